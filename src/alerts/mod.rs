@@ -0,0 +1,252 @@
+mod rule;
+
+pub use self::rule::Alert;
+pub use self::rule::Condition;
+pub use self::rule::PriceSource;
+pub use self::rule::Rule;
+pub use self::rule::RuleId;
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::Sender;
+use std::thread::sleep;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::prices::Pair;
+use crate::types::Money;
+use crate::types::Response;
+use crate::types::UtcDate;
+
+type Key = (Pair, PriceSource);
+
+#[derive(Clone, Debug)]
+struct Entry {
+  rule: Rule,
+  /// `None` until the first evaluation, then whether the rule may fire.
+  armed: Option<bool>,
+}
+
+///
+/// Evaluates price alert rules against polled prices.
+///
+/// Each `poll` fetches every watched price once and returns the alerts that
+/// fired; `run` polls at the configured interval and delivers alerts to a
+/// callback, and `run_channel` sends them to a channel.
+///
+/// ```no_run
+/// # use coinbase::prelude::*;
+/// # use coinbase::alerts::AlertEngine;
+/// # use coinbase::alerts::Rule;
+/// # use rust_decimal::Decimal;
+/// # use std::sync::atomic::AtomicBool;
+/// # use std::time::Duration;
+/// # fn run(client: &Client) -> Result<(), Error> {
+/// let mut engine = AlertEngine::new().interval(Duration::from_secs(30));
+///
+/// engine.add(Rule::above("BTC", "USD", Decimal::new(100_000, 0)).hysteresis(Decimal::ONE));
+/// engine.add(Rule::change("BTC", "USD", Decimal::new(-5, 0), Duration::from_secs(3600)));
+///
+/// engine.run(client, &AtomicBool::new(false), |alert| {
+///   println!("{} {:?} at {}", alert.base, alert.condition, alert.price);
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct AlertEngine {
+  rules: BTreeMap<RuleId, Entry>,
+  next: RuleId,
+  interval: Duration,
+  history: HashMap<Key, VecDeque<(UtcDate, Decimal)>>,
+}
+
+impl Default for AlertEngine {
+  fn default() -> Self {
+    Self {
+      rules: BTreeMap::new(),
+      next: 0,
+      interval: Duration::from_secs(10),
+      history: HashMap::new(),
+    }
+  }
+}
+
+impl AlertEngine {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Sets the time between polls of `run` (defaults to 10s).
+  pub fn interval(mut self, interval: Duration) -> Self {
+    self.interval = interval;
+    self
+  }
+
+  /// Registers a rule, returning its id.
+  pub fn add(&mut self, rule: Rule) -> RuleId {
+    let id: RuleId = self.next;
+
+    self.next += 1;
+    self.rules.insert(id, Entry { rule, armed: None });
+
+    id
+  }
+
+  /// Removes a rule, returning it if it was registered.
+  pub fn remove(&mut self, id: RuleId) -> Option<Rule> {
+    self.rules.remove(&id).map(|entry| entry.rule)
+  }
+
+  /// Returns the registered rules.
+  pub fn rules(&self) -> impl Iterator<Item = (RuleId, &Rule)> {
+    self.rules.iter().map(|(id, entry)| (*id, &entry.rule))
+  }
+
+  /// Evaluates the rules watching `source` of `base`-`quote` against `price`
+  /// observed at `at`, returning the alerts that fired.
+  pub fn evaluate(&mut self, base: &str, quote: &str, source: PriceSource, price: Decimal, at: UtcDate) -> Vec<Alert> {
+    let pair: Pair = (base.to_uppercase(), quote.to_uppercase());
+    let key: Key = (pair.clone(), source);
+
+    let window: Duration = self
+      .rules
+      .values()
+      .filter(|entry| watches(&entry.rule, &key))
+      .map(|entry| entry.rule.window())
+      .max()
+      .unwrap_or_default();
+
+    let history: &mut VecDeque<(UtcDate, Decimal)> = self.history.entry(key.clone()).or_default();
+
+    while history.front().is_some_and(|(time, _)| since(at, *time) > window) {
+      history.pop_front();
+    }
+
+    let mut alerts: Vec<Alert> = Vec::new();
+
+    for (id, entry) in self.rules.iter_mut().filter(|(_, entry)| watches(&entry.rule, &key)) {
+      let change: Option<Decimal> = history
+        .iter()
+        .find(|(time, _)| since(at, *time) <= entry.rule.window())
+        .filter(|(_, base)| !base.is_zero())
+        .map(|(_, base)| ((price - base) / base * Decimal::ONE_HUNDRED).round_dp(4).normalize());
+
+      let (holds, rearm): (bool, bool) = match entry.rule.check(price, change) {
+        Some(check) => check,
+        None => {
+          // Without history nothing has changed yet, so the first change counts.
+          entry.armed.get_or_insert(true);
+          continue;
+        }
+      };
+
+      match entry.armed {
+        Some(true) if holds => {
+          entry.armed = Some(false);
+          alerts.push(Alert {
+            rule: *id,
+            base: pair.0.clone(),
+            quote: pair.1.clone(),
+            source,
+            condition: entry.rule.condition.clone(),
+            price,
+            change: change.filter(|_| entry.rule.window() > Duration::ZERO),
+            time: at,
+          });
+        }
+        Some(false) if rearm => entry.armed = Some(true),
+        None => entry.armed = Some(!holds),
+        _ => {}
+      }
+    }
+
+    history.push_back((at, price));
+
+    alerts
+  }
+
+  /// Fetches every watched price once and evaluates the rules.
+  pub fn poll(&mut self, client: &Client) -> Result<Vec<Alert>, Error> {
+    let mut keys: Vec<Key> = self
+      .rules
+      .values()
+      .map(|entry| ((entry.rule.base.clone(), entry.rule.quote.clone()), entry.rule.source))
+      .collect();
+
+    keys.sort();
+    keys.dedup();
+
+    let mut alerts: Vec<Alert> = Vec::new();
+
+    for ((base, quote), source) in keys {
+      let response: Response<Money> = match source {
+        PriceSource::Spot => client.spot_price(&base, &quote)?,
+        PriceSource::Buy => client.buy_price(&base, &quote)?,
+        PriceSource::Sell => client.sell_price(&base, &quote)?,
+      };
+
+      let price: Money = response.into_result()?;
+
+      alerts.extend(self.evaluate(&base, &quote, source, crate::decimal(price.amount), Utc::now()));
+    }
+
+    Ok(alerts)
+  }
+
+  /// Polls every interval until `stop` is set, calling `callback` with each
+  /// alert.
+  ///
+  /// A failed poll ends the run with its error; rule state is kept, so the
+  /// run can be resumed.
+  pub fn run<F: FnMut(&Alert)>(&mut self, client: &Client, stop: &AtomicBool, mut callback: F) -> Result<(), Error> {
+    self.run_with(client, stop, |alert| {
+      callback(&alert);
+      true
+    })
+  }
+
+  /// Polls every interval until `stop` is set or the receiver of `sender`
+  /// is dropped, sending each alert to the channel.
+  pub fn run_channel(&mut self, client: &Client, stop: &AtomicBool, sender: Sender<Alert>) -> Result<(), Error> {
+    self.run_with(client, stop, |alert| sender.send(alert).is_ok())
+  }
+
+  fn run_with<F: FnMut(Alert) -> bool>(
+    &mut self,
+    client: &Client,
+    stop: &AtomicBool,
+    mut deliver: F,
+  ) -> Result<(), Error> {
+    while !stop.load(Ordering::Relaxed) {
+      let start: Instant = Instant::now();
+
+      for alert in self.poll(client)? {
+        if !deliver(alert) {
+          return Ok(());
+        }
+      }
+
+      sleep(self.interval.saturating_sub(start.elapsed()));
+    }
+
+    Ok(())
+  }
+}
+
+fn watches(rule: &Rule, ((base, quote), source): &Key) -> bool {
+  rule.source == *source && rule.base == *base && rule.quote == *quote
+}
+
+// Time elapsed from `time` to `at`, zero if `at` is earlier.
+fn since(at: UtcDate, time: UtcDate) -> Duration {
+  (at - time).to_std().unwrap_or_default()
+}
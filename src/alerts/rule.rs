@@ -0,0 +1,137 @@
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+use crate::types::UtcDate;
+
+/// Identifies a rule registered with an `AlertEngine`.
+pub type RuleId = usize;
+
+///
+/// Which price of a currency pair a rule watches.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceSource {
+  #[default]
+  Spot,
+  Buy,
+  Sell,
+}
+
+///
+/// When a rule fires.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Condition {
+  /// The price rises to or above the threshold.
+  Above(Decimal),
+  /// The price falls to or below the threshold.
+  Below(Decimal),
+  /// The price changes by at least `percent` (a rise if positive, a drop if
+  /// negative) compared to the oldest price seen within `window`.
+  Change { percent: Decimal, window: Duration },
+}
+
+///
+/// A price alert rule for one currency pair.
+///
+/// A rule fires when its condition becomes true, and fires again only after
+/// the price (or change) has moved back by `hysteresis` percent of the
+/// threshold. A condition that is already true when the rule is first
+/// evaluated does not fire.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Rule {
+  pub base: String,
+  pub quote: String,
+  pub source: PriceSource,
+  pub condition: Condition,
+  /// Percent of the threshold to move back before re-arming.
+  pub hysteresis: Decimal,
+}
+
+impl Rule {
+  pub fn new(base: &str, quote: &str, condition: Condition) -> Self {
+    Self {
+      base: base.to_uppercase(),
+      quote: quote.to_uppercase(),
+      source: PriceSource::Spot,
+      condition,
+      hysteresis: Decimal::ZERO,
+    }
+  }
+
+  /// Fires when the price rises to or above `threshold`.
+  pub fn above(base: &str, quote: &str, threshold: Decimal) -> Self {
+    Self::new(base, quote, Condition::Above(threshold))
+  }
+
+  /// Fires when the price falls to or below `threshold`.
+  pub fn below(base: &str, quote: &str, threshold: Decimal) -> Self {
+    Self::new(base, quote, Condition::Below(threshold))
+  }
+
+  /// Fires when the price changes by `percent` within `window`.
+  pub fn change(base: &str, quote: &str, percent: Decimal, window: Duration) -> Self {
+    Self::new(base, quote, Condition::Change { percent, window })
+  }
+
+  /// Sets the price the rule watches (defaults to the spot price).
+  pub fn source(mut self, source: PriceSource) -> Self {
+    self.source = source;
+    self
+  }
+
+  /// Sets the hysteresis, in percent of the threshold (defaults to 0).
+  pub fn hysteresis(mut self, percent: Decimal) -> Self {
+    self.hysteresis = percent.abs();
+    self
+  }
+
+  /// Returns the window of past prices the rule needs.
+  pub(crate) fn window(&self) -> Duration {
+    match self.condition {
+      Condition::Change { window, .. } => window,
+      _ => Duration::ZERO,
+    }
+  }
+
+  /// Returns whether the condition holds and whether the rule may re-arm,
+  /// given the `price` and its `change` in percent over the window.
+  pub(crate) fn check(&self, price: Decimal, change: Option<Decimal>) -> Option<(bool, bool)> {
+    let margin: Decimal = self.hysteresis / Decimal::ONE_HUNDRED;
+
+    match self.condition {
+      Condition::Above(threshold) => Some((price >= threshold, price < threshold - threshold.abs() * margin)),
+      Condition::Below(threshold) => Some((price <= threshold, price > threshold + threshold.abs() * margin)),
+      Condition::Change { percent, .. } => {
+        let change: Decimal = change?;
+        let rearm: Decimal = percent * (Decimal::ONE - margin);
+
+        if percent.is_sign_negative() {
+          Some((change <= percent, change > rearm))
+        } else {
+          Some((change >= percent, change < rearm))
+        }
+      }
+    }
+  }
+}
+
+///
+/// A fired rule.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Alert {
+  pub rule: RuleId,
+  pub base: String,
+  pub quote: String,
+  pub source: PriceSource,
+  pub condition: Condition,
+  pub price: Decimal,
+  /// Change in percent over the window, for `Condition::Change` rules.
+  pub change: Option<Decimal>,
+  /// Time of the price sample that fired the rule.
+  pub time: UtcDate,
+}
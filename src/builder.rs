@@ -0,0 +1,258 @@
+use reqwest::Client as Http;
+use reqwest::ClientBuilder as HttpBuilder;
+use reqwest::Proxy;
+use reqwest::Url;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::cache::Cache;
+use crate::client::Client;
+use crate::client::ENDPOINT;
+use crate::client::MAX_RETRY_AFTER;
+use crate::client::RATE_LIMIT_RETRIES;
+use crate::client::U_AGENT;
+use crate::client::VERSION;
+use crate::error::Error;
+use crate::metrics::MetricsSink;
+use crate::metrics::NoopMetrics;
+use crate::middleware::Middleware;
+use crate::ratelimit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::secret::Secret;
+use crate::transport::HttpTransport;
+use crate::transport::Transport;
+use crate::types::Language;
+
+///
+/// Configures and creates a `Client`.
+///
+/// ```no_run
+/// # use coinbase::prelude::*;
+/// # use std::time::Duration;
+/// let client = Client::builder()
+///   .base_url("http://localhost:8080/v2/")
+///   .version("2019-11-15")
+///   .timeout(Duration::from_secs(10))
+///   .build()
+///   .unwrap();
+/// ```
+///
+#[derive(Debug)]
+pub struct ClientBuilder {
+  key: Secret,
+  secret: Secret,
+  base_url: String,
+  language: Language,
+  uagent: String,
+  version: String,
+  timeout: Option<Duration>,
+  connect_timeout: Option<Duration>,
+  proxies: Vec<Proxy>,
+  transport: Option<Arc<dyn Transport>>,
+  limiter: Option<RateLimiter>,
+  rate_limit_retries: u32,
+  max_retry_after: Duration,
+  retry: RetryPolicy,
+  middleware: Vec<Arc<dyn Middleware>>,
+  metrics: Arc<dyn MetricsSink>,
+  cache: Option<Cache>,
+}
+
+impl Default for ClientBuilder {
+  fn default() -> Self {
+    Self {
+      key: Default::default(),
+      secret: Default::default(),
+      base_url: ENDPOINT.to_owned(),
+      language: Default::default(),
+      uagent: U_AGENT.to_owned(),
+      version: VERSION.to_owned(),
+      timeout: None,
+      connect_timeout: None,
+      proxies: Vec::new(),
+      transport: None,
+      limiter: None,
+      rate_limit_retries: RATE_LIMIT_RETRIES,
+      max_retry_after: MAX_RETRY_AFTER,
+      retry: RetryPolicy::none(),
+      middleware: Vec::new(),
+      metrics: Arc::new(NoopMetrics),
+      cache: None,
+    }
+  }
+}
+
+impl ClientBuilder {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Sets the API key and secret used to sign requests.
+  pub fn credentials(mut self, key: &str, secret: &str) -> Self {
+    self.key = Secret::new(key);
+    self.secret = Secret::new(secret);
+    self
+  }
+
+  /// Sets the base URL of the API (defaults to `https://api.coinbase.com/v2/`).
+  pub fn base_url(mut self, base_url: &str) -> Self {
+    self.base_url = base_url.to_owned();
+    self
+  }
+
+  /// Sets the `Accept-Language` of responses.
+  pub fn language(mut self, language: Language) -> Self {
+    self.language = language;
+    self
+  }
+
+  /// Sets the `User-Agent` header.
+  pub fn user_agent(mut self, uagent: &str) -> Self {
+    self.uagent = uagent.to_owned();
+    self
+  }
+
+  /// Sets the `CB-VERSION` header.
+  pub fn version(mut self, version: &str) -> Self {
+    self.version = version.to_owned();
+    self
+  }
+
+  /// Sets the total timeout of a request.
+  ///
+  /// Ignored when a custom HTTP client or transport is provided.
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Sets the timeout for connecting to the server.
+  ///
+  /// Ignored when a custom HTTP client or transport is provided.
+  pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+    self.connect_timeout = Some(timeout);
+    self
+  }
+
+  /// Adds a proxy for requests.
+  ///
+  /// Ignored when a custom HTTP client or transport is provided.
+  pub fn proxy(mut self, proxy: Proxy) -> Self {
+    self.proxies.push(proxy);
+    self
+  }
+
+  /// Uses a preconfigured `reqwest` client to send requests.
+  pub fn http_client(self, http: Http) -> Self {
+    self.transport(HttpTransport::new(http))
+  }
+
+  /// Uses a custom `Transport` to send requests.
+  ///
+  /// Timeouts and proxies are ignored when a custom transport is provided.
+  pub fn transport<T: Transport + 'static>(mut self, transport: T) -> Self {
+    self.transport = Some(Arc::new(transport));
+    self
+  }
+
+  /// Limits the client to bursts of `requests` requests, refilled evenly over `per`.
+  ///
+  /// Requests block until the limiter allows them to be sent.
+  pub fn rate_limit(mut self, requests: u32, per: Duration) -> Self {
+    self.limiter = Some(RateLimiter::new(requests, per));
+    self
+  }
+
+  /// Sets how many times a request is retried after a `429 Too Many Requests`
+  /// response (defaults to 3). The delay is taken from the `Retry-After` header.
+  pub fn rate_limit_retries(mut self, retries: u32) -> Self {
+    self.rate_limit_retries = retries;
+    self
+  }
+
+  /// Sets the longest `Retry-After` delay waited for (defaults to 60 seconds).
+  /// A `429` asking for a longer delay is returned instead.
+  pub fn max_retry_after(mut self, max: Duration) -> Self {
+    self.max_retry_after = max;
+    self
+  }
+
+  /// Sets the policy for retrying failed idempotent requests (defaults to
+  /// `RetryPolicy::none()`; `RetryPolicy::default()` retries server errors).
+  pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+    self.retry = retry;
+    self
+  }
+
+  /// Registers a middleware; middleware runs in registration order.
+  pub fn middleware<M: Middleware + 'static>(mut self, middleware: M) -> Self {
+    self.middleware.push(Arc::new(middleware));
+    self
+  }
+
+  /// Reports request metrics to `metrics` (defaults to `NoopMetrics`).
+  pub fn metrics<M: MetricsSink + 'static>(mut self, metrics: M) -> Self {
+    self.metrics = Arc::new(metrics);
+    self
+  }
+
+  /// Caches `GET` responses of the endpoints configured in `cache`.
+  pub fn cache(mut self, cache: Cache) -> Self {
+    self.cache = Some(cache);
+    self
+  }
+
+  pub fn build(self) -> Result<Client, Error> {
+    let mut base_url: String = self.base_url;
+
+    if !base_url.ends_with('/') {
+      base_url.push('/');
+    }
+
+    let endpoint: Url = Url::parse(&base_url)?;
+
+    if endpoint.cannot_be_a_base() {
+      return Err(Error::Config(format!("Invalid base URL: {}", base_url)));
+    }
+
+    let transport: Arc<dyn Transport> = match self.transport {
+      Some(transport) => transport,
+      None => {
+        let mut builder: HttpBuilder = Http::builder();
+
+        if let Some(timeout) = self.timeout {
+          builder = builder.timeout(timeout);
+        }
+
+        if let Some(timeout) = self.connect_timeout {
+          builder = builder.connect_timeout(timeout);
+        }
+
+        for proxy in self.proxies {
+          builder = builder.proxy(proxy);
+        }
+
+        Arc::new(HttpTransport::new(builder.build()?))
+      }
+    };
+
+    Ok(Client {
+      transport,
+      key: self.key,
+      secret: self.secret,
+      endpoint,
+      limiter: self.limiter,
+      rate_limit: Mutex::new(None),
+      rate_limit_retries: self.rate_limit_retries,
+      max_retry_after: self.max_retry_after,
+      retry: self.retry,
+      middleware: self.middleware,
+      metrics: self.metrics,
+      cache: self.cache,
+      language: self.language,
+      uagent: self.uagent,
+      version: self.version,
+    })
+  }
+}
@@ -0,0 +1,184 @@
+use reqwest::Url;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::endpoint::template;
+use crate::error::Error;
+use crate::transport::HttpResponse;
+
+#[derive(Debug)]
+struct Entry {
+  endpoint: String,
+  response: HttpResponse,
+  stored: Instant,
+}
+
+///
+/// An in-memory cache of `GET` responses, keyed by URL.
+///
+/// Only endpoints with a TTL are cached; by default these are the public
+/// market data endpoints. When a refresh fails, an expired response is
+/// served instead for up to `max_stale` (stale-while-error).
+///
+/// ```
+/// # use coinbase::cache::Cache;
+/// # use coinbase::prelude::*;
+/// # use std::time::Duration;
+/// let client = Client::builder()
+///   .cache(Cache::new().ttl("prices/{pair}/spot", Duration::from_secs(5)))
+///   .build()
+///   .unwrap();
+/// ```
+///
+#[derive(Debug)]
+pub struct Cache {
+  ttls: HashMap<String, Duration>,
+  historic: Option<Duration>,
+  max_stale: Option<Duration>,
+  entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl Default for Cache {
+  fn default() -> Self {
+    Self::empty()
+      .ttl("currencies", Duration::from_secs(6 * 60 * 60))
+      .ttl("exchange-rates", Duration::from_secs(60))
+      .ttl("prices/{pair}/buy", Duration::from_secs(10))
+      .ttl("prices/{pair}/sell", Duration::from_secs(10))
+      .ttl("prices/{pair}/spot", Duration::from_secs(10))
+      .historic(Duration::from_secs(24 * 60 * 60))
+      .max_stale(Duration::from_secs(60 * 60))
+  }
+}
+
+impl Cache {
+  /// Creates a cache with the default TTLs of the public market data endpoints.
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Creates a cache without any cached endpoints.
+  pub fn empty() -> Self {
+    Self {
+      ttls: HashMap::new(),
+      historic: None,
+      max_stale: None,
+      entries: Mutex::new(HashMap::new()),
+    }
+  }
+
+  /// Sets the TTL of an endpoint template (e.g. `prices/{pair}/spot`).
+  pub fn ttl(mut self, endpoint: &str, ttl: Duration) -> Self {
+    self.ttls.insert(template(endpoint), ttl);
+    self
+  }
+
+  /// Sets the TTL of historic prices (`prices/{pair}/spot?date=...`), which
+  /// never change once published.
+  pub fn historic(mut self, ttl: Duration) -> Self {
+    self.historic = Some(ttl);
+    self
+  }
+
+  /// Sets how long an expired response may be served when refreshing it fails.
+  pub fn max_stale(mut self, max_stale: Duration) -> Self {
+    self.max_stale = Some(max_stale);
+    self
+  }
+
+  /// Never serves expired responses.
+  pub fn no_stale(mut self) -> Self {
+    self.max_stale = None;
+    self
+  }
+
+  /// Removes the cached responses of an endpoint template, or of a single
+  /// path (e.g. `prices/BTC-USD/spot`).
+  pub fn invalidate(&self, endpoint: &str) {
+    let path: &str = endpoint.trim_matches('/');
+    let endpoint: String = template(endpoint);
+
+    self
+      .entries
+      .lock()
+      .expect("Poisoned Lock")
+      .retain(|url, entry| entry.endpoint != endpoint || (path != endpoint && !url.contains(path)));
+  }
+
+  /// Removes all cached responses.
+  pub fn clear(&self) {
+    self.entries.lock().expect("Poisoned Lock").clear();
+  }
+
+  /// Returns the number of cached responses.
+  pub fn len(&self) -> usize {
+    self.entries.lock().expect("Poisoned Lock").len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+
+  /// Returns the cached response of `url`, calling `fetch` when it is missing
+  /// or expired.
+  pub(crate) fn fetch<F>(&self, endpoint: &str, url: &Url, fetch: F) -> Result<HttpResponse, Error>
+  where
+    F: FnOnce() -> Result<HttpResponse, Error>,
+  {
+    let ttl: Option<Duration> = if endpoint.starts_with("prices/") && url.query_pairs().any(|(key, _)| key == "date") {
+      self.historic
+    } else {
+      self.ttls.get(endpoint).copied()
+    };
+
+    let ttl: Duration = match ttl {
+      Some(ttl) => ttl,
+      None => return fetch(),
+    };
+
+    if let Some(response) = self.get(url, ttl) {
+      return Ok(response);
+    }
+
+    let result: Result<HttpResponse, Error> = fetch();
+
+    match result {
+      Ok(ref response) if cacheable(response) => {
+        let entry: Entry = Entry {
+          endpoint: endpoint.to_owned(),
+          response: response.clone(),
+          stored: Instant::now(),
+        };
+
+        self
+          .entries
+          .lock()
+          .expect("Poisoned Lock")
+          .insert(url.as_str().to_owned(), entry);
+
+        result
+      }
+      Ok(ref response) if response.status.is_client_error() => result,
+      _ => match self.max_stale.and_then(|max_stale| self.get(url, ttl + max_stale)) {
+        Some(response) => Ok(response),
+        None => result,
+      },
+    }
+  }
+
+  fn get(&self, url: &Url, ttl: Duration) -> Option<HttpResponse> {
+    self
+      .entries
+      .lock()
+      .expect("Poisoned Lock")
+      .get(url.as_str())
+      .filter(|entry| entry.stored.elapsed() < ttl)
+      .map(|entry| entry.response.clone())
+  }
+}
+
+fn cacheable(response: &HttpResponse) -> bool {
+  response.status.is_success() && crate::error_id(&response.body).is_none()
+}
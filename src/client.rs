@@ -1,50 +1,110 @@
-use hmac::Hmac;
-use hmac::Mac;
+use chrono::NaiveDate;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderValue;
-use reqwest::Body;
-use reqwest::Client as Http;
+use reqwest::header::ACCEPT;
+use reqwest::header::ACCEPT_LANGUAGE;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::header::USER_AGENT;
 use reqwest::Method;
-use reqwest::Request;
+use reqwest::StatusCode;
 use reqwest::Url;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::from_str;
 use serde_json::to_string;
-use sha2::Sha256;
-
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FResult;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::builder::ClientBuilder;
+use crate::cache::Cache;
 use crate::error::Error;
+use crate::metrics::MetricsSink;
+use crate::metrics::NoopMetrics;
+use crate::middleware::Middleware;
+use crate::ratelimit::retry_after;
+use crate::ratelimit::RateLimitStatus;
+use crate::ratelimit::RateLimiter;
+use crate::retry::RetryPolicy;
+use crate::secret::Secret;
+use crate::trace::Trace;
+use crate::transport::HttpRequest;
+use crate::transport::HttpResponse;
+use crate::transport::HttpTransport;
+use crate::transport::Transport;
 use crate::types::*;
 
-type HmacSha = Hmac<Sha256>;
-
-const ENDPOINT: &str = "https://api.coinbase.com/v2/";
-const U_AGENT: &str = concat!("coinbase/rs/", env!("CARGO_PKG_VERSION"));
-const VERSION: &str = "2019-11-15";
+pub(crate) const ENDPOINT: &str = "https://api.coinbase.com/v2/";
+pub(crate) const U_AGENT: &str = concat!("coinbase/rs/", env!("CARGO_PKG_VERSION"));
+pub(crate) const VERSION: &str = "2019-11-15";
+pub(crate) const RATE_LIMIT_RETRIES: u32 = 3;
+pub(crate) const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+pub(crate) const PAGE_LIMIT: usize = 100;
 
-#[derive(Debug)]
 pub struct Client {
-  http: Http,
-  key: String,
-  secret: String,
+  pub(crate) transport: Arc<dyn Transport>,
+  pub(crate) key: Secret,
+  pub(crate) secret: Secret,
+  pub(crate) endpoint: Url,
+  pub(crate) limiter: Option<RateLimiter>,
+  pub(crate) rate_limit: Mutex<Option<RateLimitStatus>>,
+  pub(crate) rate_limit_retries: u32,
+  pub(crate) max_retry_after: Duration,
+  pub(crate) retry: RetryPolicy,
+  pub(crate) middleware: Vec<Arc<dyn Middleware>>,
+  pub(crate) metrics: Arc<dyn MetricsSink>,
+  pub(crate) cache: Option<Cache>,
   pub language: Language,
-  pub uagent: &'static str,
-  pub version: &'static str,
+  pub uagent: String,
+  pub version: String,
 }
 
 impl Default for Client {
   fn default() -> Self {
     Self {
-      http: Http::new(),
+      transport: Arc::new(HttpTransport::default()),
       key: Default::default(),
       secret: Default::default(),
+      endpoint: Url::parse(ENDPOINT).expect("Invalid Endpoint"),
+      limiter: None,
+      rate_limit: Mutex::new(None),
+      rate_limit_retries: RATE_LIMIT_RETRIES,
+      max_retry_after: MAX_RETRY_AFTER,
+      retry: RetryPolicy::none(),
+      middleware: Vec::new(),
+      metrics: Arc::new(NoopMetrics),
+      cache: None,
       language: Default::default(),
-      uagent: U_AGENT,
-      version: VERSION,
+      uagent: U_AGENT.to_owned(),
+      version: VERSION.to_owned(),
     }
   }
 }
 
+impl Debug for Client {
+  fn fmt(&self, f: &mut Formatter) -> FResult {
+    f.debug_struct("Client")
+      .field("key", &self.key)
+      .field("secret", &self.secret)
+      .field("endpoint", &self.endpoint.as_str())
+      .field("language", &self.language)
+      .field("uagent", &self.uagent)
+      .field("version", &self.version)
+      .field("limiter", &self.limiter)
+      .field("retry", &self.retry)
+      .field("middleware", &self.middleware)
+      .field("metrics", &self.metrics)
+      .field("cache", &self.cache)
+      .field("transport", &self.transport)
+      .finish()
+  }
+}
+
 impl Client {
   pub fn new() -> Self {
     Default::default()
@@ -52,12 +112,26 @@ impl Client {
 
   pub fn private(key: &str, secret: &str) -> Self {
     Self {
-      key: key.to_owned(),
-      secret: secret.to_owned(),
+      key: Secret::new(key),
+      secret: Secret::new(secret),
       ..Default::default()
     }
   }
 
+  pub fn builder() -> ClientBuilder {
+    ClientBuilder::new()
+  }
+
+  /// Returns the rate-limit headroom reported by the most recent response, if any.
+  pub fn rate_limit(&self) -> Option<RateLimitStatus> {
+    self.rate_limit.lock().expect("Poisoned Lock").clone()
+  }
+
+  /// Returns the response cache, if caching is enabled.
+  pub fn cache(&self) -> Option<&Cache> {
+    self.cache.as_ref()
+  }
+
   //
   // Public
   //
@@ -107,8 +181,13 @@ impl Client {
   /// Get the historic market price for supported currency pairs.
   ///
   /// https://developers.coinbase.com/api/v2#get-spot-price
-  pub fn historic_spot_price(&self, currency: &str, other: &str, date: &str) -> CBResult<Money> {
-    self.get(&format!("prices/{}-{}/spot?date={}", currency, other, date))
+  pub fn historic_spot_price(&self, currency: &str, other: &str, date: NaiveDate) -> CBResult<Money> {
+    self.get(&format!(
+      "prices/{}-{}/spot?date={}",
+      currency,
+      other,
+      date.format("%Y-%m-%d")
+    ))
   }
 
   //
@@ -173,6 +252,13 @@ impl Client {
     self.get("accounts")
   }
 
+  /// Lists all of the current user's accounts (oldest first), following pagination.
+  ///
+  /// https://developers.coinbase.com/api/v2#pagination
+  pub fn all_accounts(&self) -> Result<Vec<Account>, Error> {
+    self.get_all("accounts")
+  }
+
   /// Show current user's account.
   ///
   /// https://developers.coinbase.com/api/v2#show-an-account
@@ -246,6 +332,13 @@ impl Client {
     self.get(&format!("accounts/{}/transactions", account))
   }
 
+  /// Lists all of an account's transactions (oldest first), following pagination.
+  ///
+  /// https://developers.coinbase.com/api/v2#pagination
+  pub fn all_transactions(&self, account: &str) -> Result<Vec<Transaction>, Error> {
+    self.get_all(&format!("accounts/{}/transactions", account))
+  }
+
   /// Show an individual transaction for an account
   ///
   /// https://developers.coinbase.com/api/v2#show-a-transaction
@@ -267,6 +360,24 @@ impl Client {
     self.post(&format!("accounts/{}/transactions", account), self.serialize(data)?)
   }
 
+  /// Sends funds to a crypto address or email address.
+  ///
+  /// The request carries an idempotency token, so it is safe to retry when a
+  /// policy is set with `ClientBuilder::retry_policy`, and to replay with the
+  /// same token.
+  ///
+  /// https://developers.coinbase.com/api/v2#send-money
+  pub fn send_money(&self, account: &str, data: &SendMoney) -> CBResult<Transaction> {
+    self.create_transaction(account, data)
+  }
+
+  /// Transfers funds between two of the user's accounts.
+  ///
+  /// https://developers.coinbase.com/api/v2#transfer-money-between-accounts
+  pub fn transfer_money(&self, account: &str, data: &TransferMoney) -> CBResult<Transaction> {
+    self.create_transaction(account, data)
+  }
+
   /// Lets the recipient of a money request complete the request by sending money to the user who requested the money.
   ///
   /// https://developers.coinbase.com/api/v2#complete-request-money
@@ -302,6 +413,13 @@ impl Client {
     self.get(&format!("accounts/{}/buys", account))
   }
 
+  /// Lists all of an account's buys (oldest first), following pagination.
+  ///
+  /// https://developers.coinbase.com/api/v2#pagination
+  pub fn all_buys(&self, account: &str) -> Result<Vec<Buy>, Error> {
+    self.get_all(&format!("accounts/{}/buys", account))
+  }
+
   /// Show an individual buy.
   ///
   /// https://developers.coinbase.com/api/v2#show-a-buy
@@ -334,6 +452,13 @@ impl Client {
     self.get(&format!("accounts/{}/sells", account))
   }
 
+  /// Lists all of an account's sells (oldest first), following pagination.
+  ///
+  /// https://developers.coinbase.com/api/v2#pagination
+  pub fn all_sells(&self, account: &str) -> Result<Vec<Sell>, Error> {
+    self.get_all(&format!("accounts/{}/sells", account))
+  }
+
   /// Show an individual sell.
   ///
   /// https://developers.coinbase.com/api/v2#show-a-sell
@@ -366,6 +491,13 @@ impl Client {
     self.get(&format!("accounts/{}/deposits", account))
   }
 
+  /// Lists all of an account's deposits (oldest first), following pagination.
+  ///
+  /// https://developers.coinbase.com/api/v2#pagination
+  pub fn all_deposits(&self, account: &str) -> Result<Vec<Deposit>, Error> {
+    self.get_all(&format!("accounts/{}/deposits", account))
+  }
+
   /// Show an individual deposit.
   ///
   /// https://developers.coinbase.com/api/v2#show-a-deposit
@@ -398,6 +530,13 @@ impl Client {
     self.get(&format!("accounts/{}/withdrawals", account))
   }
 
+  /// Lists all of an account's withdrawals (oldest first), following pagination.
+  ///
+  /// https://developers.coinbase.com/api/v2#pagination
+  pub fn all_withdrawals(&self, account: &str) -> Result<Vec<Withdrawal>, Error> {
+    self.get_all(&format!("accounts/{}/withdrawals", account))
+  }
+
   /// Show an individual withdrawal.
   ///
   /// https://developers.coinbase.com/api/v2#show-a-withdrawal
@@ -442,15 +581,55 @@ impl Client {
   //
 
   fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
-    self.request(Method::GET, url!(path), String::new())
+    self.request(Method::GET, url!(&self.endpoint, path)?, String::new())
+  }
+
+  /// Fetches every page of a list endpoint, oldest first.
+  pub(crate) fn get_all<T: DeserializeOwned + Default>(&self, path: &str) -> Result<Vec<T>, Error> {
+    self.get_after(path, None)
+  }
+
+  /// Fetches every page of a list endpoint after the item with id `cursor`, oldest first.
+  pub(crate) fn get_after<T: DeserializeOwned + Default>(
+    &self,
+    path: &str,
+    cursor: Option<&str>,
+  ) -> Result<Vec<T>, Error> {
+    let mut items: Vec<T> = Vec::new();
+    let mut next: Option<String> = Some(match cursor {
+      Some(cursor) => format!("{}?limit={}&order=asc&starting_after={}", path, PAGE_LIMIT, cursor),
+      None => format!("{}?limit={}&order=asc", path, PAGE_LIMIT),
+    });
+
+    while let Some(path) = next.take() {
+      let response: Response<Vec<T>> = self.get(&path)?;
+
+      next = response
+        .pagination
+        .as_ref()
+        .and_then(|pagination| pagination.next_uri.as_deref())
+        .map(|uri| self.relative(uri));
+
+      items.extend(response.into_result()?);
+    }
+
+    Ok(items)
+  }
+
+  /// Converts an absolute API path (e.g. `/v2/accounts?...`) to a path relative to the endpoint.
+  fn relative(&self, uri: &str) -> String {
+    uri
+      .strip_prefix(self.endpoint.path())
+      .unwrap_or_else(|| uri.trim_start_matches('/'))
+      .to_owned()
   }
 
   fn post<T: DeserializeOwned, B: Into<String>>(&self, path: &str, body: B) -> Result<T, Error> {
-    self.request(Method::POST, url!(path), body.into())
+    self.request(Method::POST, url!(&self.endpoint, path)?, body.into())
   }
 
   fn put<T: DeserializeOwned, B: Into<String>>(&self, path: &str, body: B) -> Result<T, Error> {
-    self.request(Method::PUT, url!(path), body.into())
+    self.request(Method::PUT, url!(&self.endpoint, path)?, body.into())
   }
 
   fn delete<T>(&self, _path: &str) -> Result<T, Error> {
@@ -462,25 +641,142 @@ impl Client {
   }
 
   fn request<T: DeserializeOwned>(&self, method: Method, url: Url, body: String) -> Result<T, Error> {
-    let auth: HeaderMap = self.auth_headers(&method, &url, &body)?;
-
-    let request: Request = self
-      .http
-      .request(method, url)
-      .header("Accept", "application/json")
-      .header("Accept-Language", self.language.to_string())
-      .header("Content-Type", "application/json")
-      .header("User-Agent", self.uagent)
-      .header("CB-VERSION", self.version)
-      .headers(auth)
-      .body(Body::from(body))
-      .build()?;
-
-    let data: String = self.http.execute(request)?.text()?;
-
-    from_str(&data).map_err(|error| Error::JSON {
+    let response: HttpResponse = self.execute(method, url, body)?;
+
+    from_str(&response.body).map_err(|error| Error::JSON {
       error,
-      data: Some(data),
+      data: Some(response.body),
+    })
+  }
+
+  fn execute(&self, method: Method, url: Url, body: String) -> Result<HttpResponse, Error> {
+    match self.cache.as_ref() {
+      Some(cache) if method == Method::GET => {
+        let endpoint: String = crate::endpoint::template_url(&self.endpoint, &url);
+
+        cache.fetch(&endpoint, &url, || self.send(method, url.clone(), body))
+      }
+      _ => self.send(method, url, body),
+    }
+  }
+
+  fn send(&self, method: Method, url: Url, body: String) -> Result<HttpResponse, Error> {
+    let retry: bool = self.retry.applies(&method, &body);
+    let mut limited: u32 = 0;
+    let mut attempt: u32 = 1;
+    let trace: Trace = Trace::new(&method, &self.endpoint, &url);
+    let endpoint: String = crate::endpoint::template_url(&self.endpoint, &url);
+
+    loop {
+      if let Some(limiter) = self.limiter.as_ref() {
+        let wait: Duration = limiter.acquire();
+
+        if wait > Duration::from_secs(0) {
+          self.metrics.throttle(&endpoint, wait);
+        }
+      }
+
+      let mut request: HttpRequest = self.prepare(method.clone(), url.clone(), body.clone())?;
+
+      for middleware in self.middleware.iter() {
+        middleware.before_send(&mut request);
+      }
+
+      let start: Instant = Instant::now();
+      let result: Result<HttpResponse, Error> = self.transport.send(&request);
+      let latency: Duration = start.elapsed();
+
+      for middleware in self.middleware.iter() {
+        match result.as_ref() {
+          Ok(response) => middleware.after_receive(&request, response, latency),
+          Err(error) => middleware.on_error(&request, error, latency),
+        }
+      }
+
+      match result.as_ref() {
+        Ok(response) => trace.response(response, latency, attempt),
+        Err(error) => trace.error(error, latency, attempt),
+      }
+
+      self.record(&endpoint, &method, result.as_ref(), latency);
+
+      let response: HttpResponse = match result {
+        Ok(response) => response,
+        Err(error) if retry && attempt < self.retry.max_attempts && self.retry.retry_error(&error) => {
+          self.metrics.retry(&endpoint);
+          sleep(self.retry.delay(attempt));
+          attempt += 1;
+          continue;
+        }
+        Err(error) => return Err(error),
+      };
+
+      if let Some(status) = RateLimitStatus::from_headers(&response.headers) {
+        self.metrics.rate_limit(&status);
+        *self.rate_limit.lock().expect("Poisoned Lock") = Some(status);
+      }
+
+      if response.status == StatusCode::TOO_MANY_REQUESTS && limited < self.rate_limit_retries {
+        let wait: Duration = retry_after(&response.headers).unwrap_or_else(|| Duration::from_secs(1 << limited.min(6)));
+
+        // Rather than blocking for an unreasonable delay, leave it to the caller.
+        if wait > self.max_retry_after {
+          return Ok(response);
+        }
+
+        limited += 1;
+        self.metrics.throttle(&endpoint, wait);
+        sleep(wait);
+        continue;
+      }
+
+      if retry && attempt < self.retry.max_attempts && self.retry.retry_response(&response) {
+        self.metrics.retry(&endpoint);
+        sleep(self.retry.delay(attempt));
+        attempt += 1;
+        continue;
+      }
+
+      return Ok(response);
+    }
+  }
+
+  fn record(&self, endpoint: &str, method: &Method, result: Result<&HttpResponse, &Error>, latency: Duration) {
+    match result {
+      Ok(response) => {
+        self
+          .metrics
+          .request(endpoint, method, Some(response.status.as_u16()), latency);
+
+        if let Some(id) = crate::error_id(&response.body) {
+          self.metrics.error(endpoint, &id);
+        } else if response.status.is_client_error() || response.status.is_server_error() {
+          self
+            .metrics
+            .error(endpoint, &format!("status_{}", response.status.as_u16()));
+        }
+      }
+      Err(_) => {
+        self.metrics.request(endpoint, method, None, latency);
+        self.metrics.error(endpoint, "transport");
+      }
+    }
+  }
+
+  fn prepare(&self, method: Method, url: Url, body: String) -> Result<HttpRequest, Error> {
+    let mut headers: HeaderMap = self.auth_headers(&method, &url, &body)?;
+
+    headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+    headers.insert(ACCEPT_LANGUAGE, HeaderValue::from_str(&self.language.to_string())?);
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    headers.insert(USER_AGENT, HeaderValue::from_str(&self.uagent)?);
+    headers.insert("CB-VERSION", HeaderValue::from_str(&self.version)?);
+
+    Ok(HttpRequest {
+      method,
+      url,
+      headers,
+      body,
     })
   }
 
@@ -493,7 +789,7 @@ impl Client {
 
     if self.has_auth() {
       let now: i64 = crate::timestamp();
-      let key: HeaderValue = self.auth_header(&self.key)?;
+      let key: HeaderValue = self.auth_header(self.key.expose())?;
       let timestamp: HeaderValue = self.auth_header(&format!("{}", now))?;
 
       let signature: HeaderValue = {
@@ -524,14 +820,6 @@ impl Client {
   }
 
   fn auth_signature(&self, message: &str) -> Result<String, Error> {
-    let apply = |mut hmac: HmacSha| {
-      hmac.input(message.as_bytes());
-      hmac
-    };
-
-    let hmac: HmacSha = Hmac::new_varkey(self.secret.as_bytes()).map(apply)?;
-    let signed: String = format!("{:x}", hmac.result().code());
-
-    Ok(signed)
+    crate::sign(self.secret.expose(), message)
   }
 }
@@ -0,0 +1,195 @@
+use serde::de::Error as DeError;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde_json::from_str as from_json;
+use std::collections::HashMap;
+use std::env::var;
+use std::fs::read_to_string;
+use std::path::Path;
+use std::path::PathBuf;
+use toml::from_str as from_toml;
+
+use crate::builder::ClientBuilder;
+use crate::client::Client;
+use crate::error::Error;
+use crate::secret::Secret;
+use crate::types::Language;
+
+pub const ENV_KEY: &str = "COINBASE_API_KEY";
+pub const ENV_SECRET: &str = "COINBASE_API_SECRET";
+pub const ENV_LANGUAGE: &str = "COINBASE_LANGUAGE";
+pub const ENV_VERSION: &str = "COINBASE_API_VERSION";
+pub const ENV_BASE_URL: &str = "COINBASE_BASE_URL";
+pub const ENV_CONFIG: &str = "COINBASE_CONFIG";
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+///
+/// Client settings loaded from the environment or a profile file.
+///
+/// Profile files are TOML (or JSON, when the file name ends in `.json`) with
+/// one table per named profile:
+///
+/// ```toml
+/// [default]
+/// key = "..."
+/// secret = "..."
+/// language = "en"
+/// version = "2019-11-15"
+/// base_url = "https://api.coinbase.com/v2/"
+/// ```
+///
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+  key: Option<Secret>,
+  secret: Option<Secret>,
+  #[serde(default, deserialize_with = "deserialize_language")]
+  language: Option<Language>,
+  version: Option<String>,
+  base_url: Option<String>,
+}
+
+impl Profile {
+  /// Loads a profile from the `COINBASE_*` environment variables.
+  pub fn from_env() -> Result<Self, Error> {
+    let language: Option<Language> = match env(ENV_LANGUAGE) {
+      Some(language) => Some(language.parse()?),
+      None => None,
+    };
+
+    Ok(Self {
+      key: env(ENV_KEY).map(|key| Secret::new(&key)),
+      secret: env(ENV_SECRET).map(|secret| Secret::new(&secret)),
+      language,
+      version: env(ENV_VERSION),
+      base_url: env(ENV_BASE_URL),
+    })
+  }
+
+  /// Loads the named profile from a TOML or JSON profile file.
+  ///
+  /// On unix, the file must not be readable or writable by group or others.
+  pub fn from_file<P: AsRef<Path>>(path: P, name: &str) -> Result<Self, Error> {
+    let path: &Path = path.as_ref();
+
+    check_permissions(path)?;
+
+    let data: String = read_to_string(path)?;
+
+    let mut profiles: HashMap<String, Profile> = if path.extension() == Some("json".as_ref()) {
+      from_json(&data)?
+    } else {
+      from_toml(&data)?
+    };
+
+    profiles
+      .remove(name)
+      .ok_or_else(|| Error::Config(format!("Profile `{}` not found in {}", name, path.display())))
+  }
+
+  /// Returns the profile file path from `COINBASE_CONFIG`, falling back
+  /// to `~/.coinbase/config.toml`.
+  pub fn default_path() -> Option<PathBuf> {
+    env(ENV_CONFIG)
+      .map(PathBuf::from)
+      .or_else(|| env("HOME").map(|home| PathBuf::from(home).join(".coinbase").join("config.toml")))
+  }
+
+  /// Creates a `Client` configured with the settings of this profile.
+  pub fn client(self) -> Result<Client, Error> {
+    self.builder()?.build()
+  }
+
+  /// Creates a `ClientBuilder` preconfigured with the settings of this profile.
+  pub fn builder(self) -> Result<ClientBuilder, Error> {
+    let mut builder: ClientBuilder = match (&self.key, &self.secret) {
+      (Some(key), Some(secret)) => ClientBuilder::new().credentials(key.expose(), secret.expose()),
+      (None, None) => ClientBuilder::new(),
+      _ => return Err(Error::Config("Both an API key and secret are required".into())),
+    };
+
+    if let Some(language) = self.language {
+      builder = builder.language(language);
+    }
+
+    if let Some(version) = self.version.as_ref() {
+      builder = builder.version(version);
+    }
+
+    if let Some(base_url) = self.base_url.as_ref() {
+      builder = builder.base_url(base_url);
+    }
+
+    Ok(builder)
+  }
+}
+
+impl Client {
+  /// Creates a client from the `COINBASE_*` environment variables.
+  ///
+  /// `COINBASE_API_KEY` and `COINBASE_API_SECRET` are required;
+  /// `COINBASE_LANGUAGE`, `COINBASE_API_VERSION` and `COINBASE_BASE_URL`
+  /// are optional.
+  pub fn from_env() -> Result<Self, Error> {
+    let profile: Profile = Profile::from_env()?;
+
+    if profile.key.is_none() {
+      return Err(Error::Config(format!("You must set `{}`", ENV_KEY)));
+    }
+
+    if profile.secret.is_none() {
+      return Err(Error::Config(format!("You must set `{}`", ENV_SECRET)));
+    }
+
+    profile.client()
+  }
+
+  /// Creates a client from the named profile in the default profile file.
+  pub fn from_profile(name: &str) -> Result<Self, Error> {
+    let path: PathBuf = Profile::default_path().ok_or_else(|| Error::Config("No profile file found".into()))?;
+
+    Self::from_profile_file(path, name)
+  }
+
+  /// Creates a client from the named profile in the given profile file.
+  pub fn from_profile_file<P: AsRef<Path>>(path: P, name: &str) -> Result<Self, Error> {
+    Profile::from_file(path, name)?.client()
+  }
+}
+
+fn env(name: &str) -> Option<String> {
+  var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn deserialize_language<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Language>, D::Error> {
+  Option::<String>::deserialize(deserializer)?
+    .map(|language| {
+      language
+        .parse()
+        .map_err(|_| DeError::custom(format!("unknown language `{}`", language)))
+    })
+    .transpose()
+}
+
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<(), Error> {
+  use std::os::unix::fs::PermissionsExt;
+
+  let mode: u32 = path.metadata()?.permissions().mode();
+
+  if mode & 0o077 != 0 {
+    return Err(Error::Config(format!(
+      "Permissions {:o} for {} are too open; use 600",
+      mode & 0o777,
+      path.display()
+    )));
+  }
+
+  Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<(), Error> {
+  Ok(())
+}
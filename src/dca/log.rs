@@ -0,0 +1,147 @@
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::error::Error;
+use crate::types::Money;
+use crate::types::UtcDate;
+
+///
+/// The outcome of a scheduled run.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStatus {
+  /// Recorded before the buy is placed; a run left pending (e.g. by a crash
+  /// or network error) is retried with the same idempotency token.
+  Pending,
+  Placed,
+  /// Recorded instead of placing a buy in dry-run mode.
+  DryRun,
+  /// A missed run that was not caught up.
+  Skipped,
+  /// The API rejected the buy.
+  Failed,
+}
+
+///
+/// An entry of the execution log.
+///
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Execution {
+  pub plan: String,
+  pub scheduled: UtcDate,
+  pub recorded: UtcDate,
+  pub status: ExecutionStatus,
+  pub idem: String,
+  /// Id of the placed buy.
+  pub buy: Option<String>,
+  pub amount: Option<Money>,
+  pub total: Option<Money>,
+  pub error: Option<String>,
+}
+
+///
+/// Persistent, append-only storage for executions.
+///
+pub trait ExecutionLog {
+  /// Loads all executions, oldest first.
+  fn load(&mut self) -> Result<Vec<Execution>, Error>;
+
+  fn append(&mut self, execution: &Execution) -> Result<(), Error>;
+}
+
+impl<L: ExecutionLog + ?Sized> ExecutionLog for &mut L {
+  fn load(&mut self) -> Result<Vec<Execution>, Error> {
+    (**self).load()
+  }
+
+  fn append(&mut self, execution: &Execution) -> Result<(), Error> {
+    (**self).append(execution)
+  }
+}
+
+///
+/// An `ExecutionLog` kept in memory.
+///
+#[derive(Clone, Debug, Default)]
+pub struct MemoryLog {
+  executions: Vec<Execution>,
+}
+
+impl MemoryLog {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn executions(&self) -> &[Execution] {
+    &self.executions
+  }
+}
+
+impl ExecutionLog for MemoryLog {
+  fn load(&mut self) -> Result<Vec<Execution>, Error> {
+    Ok(self.executions.clone())
+  }
+
+  fn append(&mut self, execution: &Execution) -> Result<(), Error> {
+    self.executions.push(execution.clone());
+    Ok(())
+  }
+}
+
+///
+/// An `ExecutionLog` kept in a file with one JSON execution per line.
+///
+#[derive(Clone, Debug)]
+pub struct JsonLog {
+  path: PathBuf,
+}
+
+impl JsonLog {
+  pub fn new<P: AsRef<Path>>(path: P) -> Self {
+    Self {
+      path: path.as_ref().to_owned(),
+    }
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+}
+
+impl ExecutionLog for JsonLog {
+  fn load(&mut self) -> Result<Vec<Execution>, Error> {
+    let file: File = match File::open(&self.path) {
+      Ok(file) => file,
+      Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+      Err(error) => return Err(error.into()),
+    };
+
+    let mut executions: Vec<Execution> = Vec::new();
+
+    for line in BufReader::new(file).lines() {
+      let line: String = line?;
+
+      if !line.trim().is_empty() {
+        executions.push(serde_json::from_str(&line)?);
+      }
+    }
+
+    Ok(executions)
+  }
+
+  fn append(&mut self, execution: &Execution) -> Result<(), Error> {
+    let mut file: File = OpenOptions::new().create(true).append(true).open(&self.path)?;
+
+    writeln!(file, "{}", serde_json::to_string(execution)?)?;
+    file.sync_data()?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,348 @@
+mod log;
+mod schedule;
+
+pub use self::log::Execution;
+pub use self::log::ExecutionLog;
+pub use self::log::ExecutionStatus;
+pub use self::log::JsonLog;
+pub use self::log::MemoryLog;
+pub use self::schedule::Cron;
+pub use self::schedule::Schedule;
+
+use chrono::Duration;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread::sleep;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::types::Buy;
+use crate::types::BuyRequest;
+use crate::types::UtcDate;
+
+///
+/// What to do with runs that were due while the scheduler was not running.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissedRuns {
+  /// Record missed runs as skipped and only place the latest one.
+  #[default]
+  Skip,
+  /// Place every missed run, oldest first.
+  CatchUp,
+}
+
+///
+/// A recurring buy of a fixed amount.
+///
+#[derive(Clone, Debug)]
+pub struct Plan {
+  /// Unique name of the plan, used in the execution log and idempotency tokens.
+  pub id: String,
+  pub account: String,
+  /// Amount to buy, denominated in `currency` (e.g. `"50"` and `"USD"`).
+  pub amount: String,
+  pub currency: String,
+  pub payment_method: Option<String>,
+  pub schedule: Schedule,
+  /// Runs before this time are never placed.
+  pub start: UtcDate,
+  pub missed: MissedRuns,
+}
+
+impl Plan {
+  pub fn new(id: &str, account: &str, amount: &str, currency: &str, schedule: Schedule) -> Self {
+    Self {
+      id: id.to_owned(),
+      account: account.to_owned(),
+      amount: amount.to_owned(),
+      currency: currency.to_owned(),
+      payment_method: None,
+      schedule,
+      start: Utc::now(),
+      missed: MissedRuns::Skip,
+    }
+  }
+
+  /// Sets the payment method to buy with (defaults to the primary one).
+  pub fn payment_method(mut self, payment_method: &str) -> Self {
+    self.payment_method = Some(payment_method.to_owned());
+    self
+  }
+
+  /// Sets the time of the first run (defaults to now).
+  pub fn start(mut self, start: UtcDate) -> Self {
+    self.start = start;
+    self
+  }
+
+  /// Sets what to do with missed runs (defaults to `MissedRuns::Skip`).
+  pub fn missed(mut self, missed: MissedRuns) -> Self {
+    self.missed = missed;
+    self
+  }
+
+  /// Returns the idempotency token of the run scheduled at `scheduled`.
+  ///
+  /// Tokens are derived from the plan id and run time, so a run that is
+  /// retried after a crash can never place a second buy.
+  pub fn idem(&self, scheduled: UtcDate) -> String {
+    format!("dca-{}-{}", self.id, scheduled.timestamp())
+  }
+}
+
+///
+/// Places the buys of recurring plans and records each run in an
+/// `ExecutionLog`.
+///
+/// Every run is recorded as `Pending` before its buy is placed, so runs
+/// interrupted by a crash or network error are retried (with the same
+/// idempotency token) on the next tick. In dry-run mode runs are recorded as
+/// `DryRun` without placing buys; use a separate log for dry runs.
+///
+/// ```no_run
+/// # use coinbase::prelude::*;
+/// # use coinbase::dca::JsonLog;
+/// # use coinbase::dca::Plan;
+/// # use coinbase::dca::Schedule;
+/// # use coinbase::dca::Scheduler;
+/// # use std::sync::atomic::AtomicBool;
+/// # use std::time::Duration;
+/// # fn run(client: &Client, account: &str) -> Result<(), Error> {
+/// let mut scheduler = Scheduler::new(JsonLog::new("dca.jsonl"))?
+///   .plan(Plan::new("weekly-btc", account, "50", "USD", Schedule::cron("0 9 * * 1")?));
+///
+/// scheduler.run(client, &AtomicBool::new(false), Duration::from_secs(60))?;
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug)]
+pub struct Scheduler<L: ExecutionLog> {
+  plans: Vec<Plan>,
+  log: L,
+  dry_run: bool,
+  executions: Vec<Execution>,
+}
+
+impl<L: ExecutionLog> Scheduler<L> {
+  /// Creates a scheduler, loading previous executions from `log`.
+  pub fn new(mut log: L) -> Result<Self, Error> {
+    let executions: Vec<Execution> = log.load()?;
+
+    Ok(Self {
+      plans: Vec::new(),
+      log,
+      dry_run: false,
+      executions,
+    })
+  }
+
+  /// Adds a plan; plan ids must be unique.
+  pub fn plan(mut self, plan: Plan) -> Self {
+    self.plans.push(plan);
+    self
+  }
+
+  /// Records runs as `DryRun` without placing buys (defaults to false). Dry
+  /// runs must use a separate log, as they count as done.
+  pub fn dry_run(mut self, dry_run: bool) -> Self {
+    self.dry_run = dry_run;
+    self
+  }
+
+  /// Returns the plans, in the order they were added.
+  pub fn plans(&self) -> &[Plan] {
+    &self.plans
+  }
+
+  /// Returns all executions, oldest first.
+  pub fn executions(&self) -> &[Execution] {
+    &self.executions
+  }
+
+  /// Returns the execution log.
+  pub fn log(&self) -> &L {
+    &self.log
+  }
+
+  /// Returns the next run of any plan after `now`.
+  pub fn next_run(&self, now: UtcDate) -> Option<UtcDate> {
+    self
+      .plans
+      .iter()
+      .filter_map(|plan| plan.schedule.next_after(now.max(plan.start - Duration::nanoseconds(1))))
+      .min()
+  }
+
+  /// Returns the runs due at `now` for each plan, oldest first, including
+  /// pending runs to retry.
+  pub fn due(&self, now: UtcDate) -> Vec<(&Plan, Vec<UtcDate>)> {
+    let latest: HashMap<&str, &Execution> = self.latest();
+
+    self
+      .plans
+      .iter()
+      .map(|plan| {
+        let last: Option<&Execution> = latest.get(plan.id.as_str()).copied();
+        let mut runs: Vec<UtcDate> = Vec::new();
+
+        if let Some(last) = last.filter(|last| last.status == ExecutionStatus::Pending) {
+          runs.push(last.scheduled);
+        }
+
+        let mut after: UtcDate = last
+          .map(|last| last.scheduled)
+          .unwrap_or(plan.start - Duration::nanoseconds(1));
+
+        while let Some(run) = plan.schedule.next_after(after).filter(|run| *run <= now) {
+          runs.push(run);
+          after = run;
+        }
+
+        (plan, runs)
+      })
+      .collect()
+  }
+
+  /// Places (or skips) every run due at `now`, returning the executions
+  /// recorded.
+  ///
+  /// Transport errors end the tick with the run left `Pending`; API errors
+  /// are recorded as `Failed` and are not retried.
+  pub fn tick(&mut self, client: &Client, now: UtcDate) -> Result<Vec<Execution>, Error> {
+    let due: Vec<(Plan, Vec<UtcDate>)> = self
+      .due(now)
+      .into_iter()
+      .map(|(plan, runs)| (plan.clone(), runs))
+      .collect();
+
+    let mut recorded: Vec<Execution> = Vec::new();
+
+    for (plan, runs) in due {
+      let count: usize = runs.len();
+
+      for (index, scheduled) in runs.into_iter().enumerate() {
+        let skip: bool = plan.missed == MissedRuns::Skip && index + 1 < count && !self.is_pending(&plan, scheduled);
+
+        if skip {
+          recorded.push(self.record(&plan, scheduled, ExecutionStatus::Skipped)?);
+        } else if self.dry_run {
+          recorded.push(self.record(&plan, scheduled, ExecutionStatus::DryRun)?);
+        } else {
+          recorded.push(self.place(client, &plan, scheduled)?);
+        }
+      }
+    }
+
+    Ok(recorded)
+  }
+
+  /// Ticks until `stop` is set, sleeping until the next run but at most `poll`.
+  pub fn run(&mut self, client: &Client, stop: &AtomicBool, poll: std::time::Duration) -> Result<(), Error> {
+    while !stop.load(Ordering::Relaxed) {
+      let now: UtcDate = Utc::now();
+
+      self.tick(client, now)?;
+
+      let wait: std::time::Duration = self
+        .next_run(now)
+        .and_then(|next| (next - Utc::now()).to_std().ok())
+        .map_or(poll, |wait| wait.min(poll));
+
+      sleep(wait);
+    }
+
+    Ok(())
+  }
+
+  fn place(&mut self, client: &Client, plan: &Plan, scheduled: UtcDate) -> Result<Execution, Error> {
+    if !self.is_pending(plan, scheduled) {
+      self.record(plan, scheduled, ExecutionStatus::Pending)?;
+    }
+
+    let mut request: BuyRequest = BuyRequest::new(&plan.amount, &plan.currency).with_idem(&plan.idem(scheduled));
+
+    if let Some(payment_method) = plan.payment_method.as_ref() {
+      request = request.payment_method(payment_method);
+    }
+
+    let result: Result<Buy, Error> = client
+      .create_buy(&plan.account, &request)
+      .and_then(|response| response.into_result());
+
+    let mut execution: Execution = execution(plan, scheduled, ExecutionStatus::Placed);
+
+    match result {
+      Ok(buy) => {
+        execution.buy = Some(buy.meta.id);
+        execution.amount = Some(buy.amount);
+        execution.total = Some(buy.total);
+      }
+      Err(Error::API(errors)) => {
+        execution.status = ExecutionStatus::Failed;
+        execution.error = Some(
+          errors
+            .iter()
+            .map(|error| format!("{}: {}", error.id, error.message))
+            .collect::<Vec<String>>()
+            .join("; "),
+        );
+      }
+      Err(error) => return Err(error),
+    }
+
+    self.append(execution)
+  }
+
+  fn record(&mut self, plan: &Plan, scheduled: UtcDate, status: ExecutionStatus) -> Result<Execution, Error> {
+    self.append(execution(plan, scheduled, status))
+  }
+
+  fn append(&mut self, execution: Execution) -> Result<Execution, Error> {
+    self.log.append(&execution)?;
+    self.executions.push(execution.clone());
+
+    Ok(execution)
+  }
+
+  fn is_pending(&self, plan: &Plan, scheduled: UtcDate) -> bool {
+    self
+      .latest()
+      .get(plan.id.as_str())
+      .is_some_and(|last| last.scheduled == scheduled && last.status == ExecutionStatus::Pending)
+  }
+
+  /// Returns the latest execution of each plan.
+  fn latest(&self) -> HashMap<&str, &Execution> {
+    let mut latest: HashMap<&str, &Execution> = HashMap::new();
+
+    for execution in self.executions.iter() {
+      let newer: bool = latest
+        .get(execution.plan.as_str())
+        .is_none_or(|last| execution.scheduled >= last.scheduled);
+
+      if newer {
+        latest.insert(&execution.plan, execution);
+      }
+    }
+
+    latest
+  }
+}
+
+fn execution(plan: &Plan, scheduled: UtcDate, status: ExecutionStatus) -> Execution {
+  Execution {
+    plan: plan.id.clone(),
+    scheduled,
+    recorded: Utc::now(),
+    status,
+    idem: plan.idem(scheduled),
+    buy: None,
+    amount: None,
+    total: None,
+    error: None,
+  }
+}
@@ -0,0 +1,191 @@
+use chrono::Datelike;
+use chrono::Duration;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use chrono::Timelike;
+use chrono::Utc;
+use chrono::Weekday;
+
+use crate::error::Error;
+use crate::types::UtcDate;
+
+/// How many days `Cron::next_after` searches before giving up (covers leap days).
+const CRON_HORIZON: i64 = 5 * 366;
+
+///
+/// When a plan runs. All times are UTC.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum Schedule {
+  Daily { at: NaiveTime },
+  Weekly { weekday: Weekday, at: NaiveTime },
+  Cron(Cron),
+}
+
+impl Schedule {
+  pub fn daily(at: NaiveTime) -> Self {
+    Schedule::Daily { at }
+  }
+
+  pub fn weekly(weekday: Weekday, at: NaiveTime) -> Self {
+    Schedule::Weekly { weekday, at }
+  }
+
+  /// Parses a five field cron expression (`minute hour day month weekday`).
+  pub fn cron(expression: &str) -> Result<Self, Error> {
+    expression.parse().map(Schedule::Cron)
+  }
+
+  /// Returns the first run strictly after `after`, if any.
+  pub fn next_after(&self, after: UtcDate) -> Option<UtcDate> {
+    match self {
+      Schedule::Daily { at } => {
+        let run: UtcDate = at_time(after.date_naive(), *at);
+
+        Some(if run > after { run } else { run + Duration::days(1) })
+      }
+      Schedule::Weekly { weekday, at } => {
+        let offset: i64 =
+          (weekday.num_days_from_monday() as i64 - after.weekday().num_days_from_monday() as i64).rem_euclid(7);
+        let run: UtcDate = at_time(after.date_naive() + Duration::days(offset), *at);
+
+        Some(if run > after { run } else { run + Duration::days(7) })
+      }
+      Schedule::Cron(cron) => cron.next_after(after),
+    }
+  }
+}
+
+///
+/// A parsed cron expression.
+///
+/// Each field accepts `*`, values, ranges (`1-5`), steps (`*/15`, `0-30/10`)
+/// and comma separated lists. Weekdays are `0-7` with both `0` and `7` being
+/// Sunday. As in cron, when both the day and weekday fields are restricted a
+/// day matching either one runs.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cron {
+  minutes: u64,
+  hours: u64,
+  days: u64,
+  months: u64,
+  weekdays: u64,
+  any_day: bool,
+  any_weekday: bool,
+}
+
+impl std::str::FromStr for Cron {
+  type Err = Error;
+
+  fn from_str(expression: &str) -> Result<Self, Self::Err> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+
+    if fields.len() != 5 {
+      return Err(Error::Schedule(format!("Expected 5 cron fields: {}", expression)));
+    }
+
+    let weekdays: u64 = field(fields[4], 0, 7)?;
+
+    Ok(Self {
+      minutes: field(fields[0], 0, 59)?,
+      hours: field(fields[1], 0, 23)?,
+      days: field(fields[2], 1, 31)?,
+      months: field(fields[3], 1, 12)?,
+      weekdays: (weekdays | weekdays >> 7) & 0x7f,
+      any_day: fields[2] == "*",
+      any_weekday: fields[4] == "*",
+    })
+  }
+}
+
+impl Cron {
+  /// Returns the first matching minute strictly after `after`, if one exists
+  /// within the next five years.
+  pub fn next_after(&self, after: UtcDate) -> Option<UtcDate> {
+    let start: UtcDate = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+    for offset in 0..CRON_HORIZON {
+      let date: NaiveDate = start.date_naive() + Duration::days(offset);
+
+      if !self.matches_day(date) {
+        continue;
+      }
+
+      let (hour, minute): (u32, u32) = if offset == 0 {
+        (start.hour(), start.minute())
+      } else {
+        (0, 0)
+      };
+
+      for h in (hour..24).filter(|h| bit(self.hours, *h)) {
+        let from: u32 = if h == hour { minute } else { 0 };
+
+        if let Some(m) = (from..60).find(|m| bit(self.minutes, *m)) {
+          return Some(at_time(date, NaiveTime::from_hms_opt(h, m, 0)?));
+        }
+      }
+    }
+
+    None
+  }
+
+  fn matches_day(&self, date: NaiveDate) -> bool {
+    if !bit(self.months, date.month()) {
+      return false;
+    }
+
+    let day: bool = bit(self.days, date.day());
+    let weekday: bool = bit(self.weekdays, date.weekday().num_days_from_sunday());
+
+    match (self.any_day, self.any_weekday) {
+      (true, true) => true,
+      (true, false) => weekday,
+      (false, true) => day,
+      (false, false) => day || weekday,
+    }
+  }
+}
+
+fn at_time(date: NaiveDate, time: NaiveTime) -> UtcDate {
+  Utc.from_utc_datetime(&date.and_time(time))
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+  mask & (1 << value) != 0
+}
+
+/// Parses one cron field into a bit mask of the allowed values.
+fn field(field: &str, min: u32, max: u32) -> Result<u64, Error> {
+  let invalid = || Error::Schedule(format!("Invalid cron field: {}", field));
+  let mut mask: u64 = 0;
+
+  for item in field.split(',') {
+    let (range, step): (&str, u32) = match item.split_once('/') {
+      Some((range, step)) => (range, step.parse().map_err(|_| invalid())?),
+      None => (item, 1),
+    };
+
+    let (from, to): (u32, u32) = match range {
+      "*" => (min, max),
+      _ => match range.split_once('-') {
+        Some((from, to)) => (from.parse().map_err(|_| invalid())?, to.parse().map_err(|_| invalid())?),
+        None => {
+          let value: u32 = range.parse().map_err(|_| invalid())?;
+          (value, if step > 1 { max } else { value })
+        }
+      },
+    };
+
+    if step == 0 || from < min || to > max || from > to {
+      return Err(invalid());
+    }
+
+    for value in (from..=to).step_by(step as usize) {
+      mask |= 1 << value;
+    }
+  }
+
+  Ok(mask)
+}
@@ -0,0 +1,55 @@
+use reqwest::Url;
+
+const COLLECTIONS: &[&str] = &[
+  "accounts",
+  "addresses",
+  "buys",
+  "deposits",
+  "notifications",
+  "payment-methods",
+  "sells",
+  "transactions",
+  "users",
+  "withdrawals",
+];
+
+/// Returns the endpoint template of a request path relative to the API
+/// base, replacing resource ids with placeholders.
+///
+/// ```
+/// # use coinbase::endpoint::template;
+/// assert_eq!(template("accounts/abc/transactions?limit=10"), "accounts/{id}/transactions");
+/// assert_eq!(template("prices/BTC-USD/spot"), "prices/{pair}/spot");
+/// ```
+pub fn template(path: &str) -> String {
+  let path: &str = path.split('?').next().unwrap_or_default().trim_matches('/');
+  let mut previous: &str = "";
+
+  path
+    .split('/')
+    .map(|segment| {
+      let placeholder: &str = match previous {
+        "prices" => "{pair}",
+        _ if COLLECTIONS.contains(&previous) => "{id}",
+        _ => segment,
+      };
+
+      previous = segment;
+      placeholder
+    })
+    .collect::<Vec<_>>()
+    .join("/")
+}
+
+/// Returns the endpoint template of `url` relative to the API `base`.
+pub fn template_url(base: &Url, url: &Url) -> String {
+  template(url.path().strip_prefix(base.path()).unwrap_or_else(|| url.path()))
+}
+
+/// Returns the pagination cursor (`starting_after` or `ending_before`) of `url`.
+pub fn cursor(url: &Url) -> Option<String> {
+  url
+    .query_pairs()
+    .find(|(key, _)| key == "starting_after" || key == "ending_before")
+    .map(|(_, value)| value.into_owned())
+}
@@ -1,15 +1,31 @@
 use crypto_mac::InvalidKeyLength;
+use csv::Error as CSV;
 use reqwest::header::InvalidHeaderValue;
 use reqwest::Error as Reqwest;
+use reqwest::Method;
+use reqwest::Url;
+use reqwest::UrlError;
 use serde::Deserialize;
 use serde::Deserializer;
 use serde_json::Error as Serde;
+use std::io::Error as IO;
+use toml::de::Error as TOML;
+
+use crate::types::ErrorMessage;
 
 #[derive(Debug)]
 pub enum Error {
   HTTP(Reqwest),
   JSON { error: Serde, data: Option<String> },
   InvalidAuth,
+  URL(UrlError),
+  IO(IO),
+  TOML(TOML),
+  Config(String),
+  Unmatched { method: Method, url: Url },
+  API(Vec<ErrorMessage>),
+  CSV(CSV),
+  Schedule(String),
 }
 
 impl From<InvalidKeyLength> for Error {
@@ -39,6 +55,30 @@ impl From<Reqwest> for Error {
   }
 }
 
+impl From<UrlError> for Error {
+  fn from(other: UrlError) -> Self {
+    Error::URL(other)
+  }
+}
+
+impl From<IO> for Error {
+  fn from(other: IO) -> Self {
+    Error::IO(other)
+  }
+}
+
+impl From<CSV> for Error {
+  fn from(other: CSV) -> Self {
+    Error::CSV(other)
+  }
+}
+
+impl From<TOML> for Error {
+  fn from(other: TOML) -> Self {
+    Error::TOML(other)
+  }
+}
+
 impl<'de> Deserialize<'de> for Error {
   fn deserialize<D: Deserializer<'de>>(_deserializer: D) -> Result<Self, D::Error> {
     unimplemented!();
@@ -0,0 +1,182 @@
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::name;
+use crate::types::Buy;
+use crate::types::Deposit;
+use crate::types::Money;
+use crate::types::ResourceMeta;
+use crate::types::ResourceType;
+use crate::types::Sell;
+use crate::types::Transaction;
+use crate::types::TransactionType;
+use crate::types::UtcDate;
+use crate::types::ValueMap;
+use crate::types::Withdrawal;
+
+///
+/// A normalized row of account activity.
+///
+/// Activity is created from transactions, buys, sells, deposits and
+/// withdrawals; amounts are signed (negative when leaving the account).
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Activity {
+  pub timestamp: UtcDate,
+  pub account: String,
+  pub resource: ResourceType,
+  pub id: String,
+  /// The transaction type (e.g. `send`), or the resource name for buys,
+  /// sells, deposits and withdrawals.
+  pub kind: String,
+  pub status: String,
+  pub amount: Decimal,
+  pub currency: String,
+  pub native_amount: Option<Decimal>,
+  pub native_currency: Option<String>,
+  pub fee: Option<Decimal>,
+  pub fee_currency: Option<String>,
+  pub counterparty: Option<String>,
+  pub network_hash: Option<String>,
+  pub description: Option<String>,
+}
+
+impl Activity {
+  pub fn from_transaction(account: &str, transaction: &Transaction) -> Self {
+    let fee: Option<(Decimal, String)> = transaction
+      .network
+      .as_ref()
+      .and_then(|network| network.get("transaction_fee"))
+      .and_then(|fee| {
+        Some((
+          value_decimal(fee.get("amount")?)?,
+          fee.get("currency")?.as_str()?.to_owned(),
+        ))
+      });
+
+    let counterparty: Option<String> = if transaction.amount.amount < 0.0 {
+      party(transaction.to.as_ref())
+    } else {
+      party(transaction.from.as_ref())
+    }
+    .or_else(|| party(transaction.to.as_ref()))
+    .or_else(|| party(transaction.address.as_ref()));
+
+    Self {
+      timestamp: transaction.meta.created(),
+      account: account.to_owned(),
+      resource: ResourceType::Transaction,
+      id: transaction.meta.id.clone(),
+      kind: name(&transaction.kind),
+      status: name(&transaction.status),
+      amount: crate::decimal(transaction.amount.amount),
+      currency: transaction.amount.currency.clone(),
+      native_amount: Some(crate::decimal(transaction.native_amount.amount)),
+      native_currency: Some(transaction.native_amount.currency.clone()),
+      fee: fee.as_ref().map(|(amount, _)| *amount),
+      fee_currency: fee.map(|(_, currency)| currency),
+      counterparty,
+      network_hash: transaction
+        .network
+        .as_ref()
+        .and_then(|network| network.get("hash"))
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned),
+      description: transaction.description.clone(),
+    }
+  }
+
+  pub fn from_buy(account: &str, buy: &Buy) -> Self {
+    Self::trade(account, ResourceType::Buy, &buy.meta, name(&buy.status)).amounts(
+      &buy.amount,
+      Some(&buy.total),
+      &buy.fee,
+      1.0,
+    )
+  }
+
+  pub fn from_sell(account: &str, sell: &Sell) -> Self {
+    Self::trade(account, ResourceType::Sell, &sell.meta, name(&sell.status)).amounts(
+      &sell.amount,
+      Some(&sell.total),
+      &sell.fee,
+      -1.0,
+    )
+  }
+
+  pub fn from_deposit(account: &str, deposit: &Deposit) -> Self {
+    Self::trade(account, ResourceType::Deposit, &deposit.meta, name(&deposit.status)).amounts(
+      &deposit.amount,
+      None,
+      &deposit.fee,
+      1.0,
+    )
+  }
+
+  pub fn from_withdrawal(account: &str, withdrawal: &Withdrawal) -> Self {
+    Self::trade(
+      account,
+      ResourceType::Withdrawal,
+      &withdrawal.meta,
+      name(&withdrawal.status),
+    )
+    .amounts(&withdrawal.amount, None, &withdrawal.fee, -1.0)
+  }
+
+  /// Returns the transaction type, if the activity is a transaction.
+  pub fn transaction_type(&self) -> Option<TransactionType> {
+    match self.resource {
+      ResourceType::Transaction => serde_json::from_value(Value::String(self.kind.clone())).ok(),
+      _ => None,
+    }
+  }
+
+  fn trade(account: &str, resource: ResourceType, meta: &ResourceMeta, status: String) -> Self {
+    Self {
+      timestamp: meta.created(),
+      account: account.to_owned(),
+      kind: name(&resource),
+      resource,
+      id: meta.id.clone(),
+      status,
+      amount: Decimal::ZERO,
+      currency: String::new(),
+      native_amount: None,
+      native_currency: None,
+      fee: None,
+      fee_currency: None,
+      counterparty: None,
+      network_hash: None,
+      description: None,
+    }
+  }
+
+  fn amounts(mut self, amount: &Money, native: Option<&Money>, fee: &Money, sign: f64) -> Self {
+    self.amount = crate::decimal(amount.amount.abs() * sign);
+    self.currency = amount.currency.clone();
+    self.native_amount = native.map(|money| crate::decimal(money.amount.abs() * sign));
+    self.native_currency = native.map(|money| money.currency.clone());
+    self.fee = Some(crate::decimal(fee.amount));
+    self.fee_currency = Some(fee.currency.clone());
+    self
+  }
+}
+
+fn party(map: Option<&ValueMap>) -> Option<String> {
+  let map: &ValueMap = map?;
+
+  ["email", "address", "name", "id"]
+    .iter()
+    .filter_map(|key| map.get(*key))
+    .filter_map(Value::as_str)
+    .find(|value| !value.is_empty())
+    .map(ToOwned::to_owned)
+}
+
+fn value_decimal(value: &Value) -> Option<Decimal> {
+  match value {
+    Value::String(value) => value.parse().ok(),
+    Value::Number(value) => value.as_f64().map(crate::decimal),
+    _ => None,
+  }
+}
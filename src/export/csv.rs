@@ -0,0 +1,22 @@
+use csv::Writer;
+use std::io::Write;
+
+use crate::error::Error;
+use crate::export::Activity;
+use crate::export::Column;
+
+/// Writes `activity` as CSV with a header row of `columns`. Returns the
+/// number of rows written.
+pub fn write_csv<W: Write>(activity: &[Activity], columns: &[Column], writer: W) -> Result<usize, Error> {
+  let mut writer: Writer<W> = Writer::from_writer(writer);
+
+  writer.write_record(columns.iter().map(|column| column.name()))?;
+
+  for item in activity {
+    writer.write_record(columns.iter().map(|column| column.value(item)))?;
+  }
+
+  writer.flush()?;
+
+  Ok(activity.len())
+}
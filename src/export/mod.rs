@@ -0,0 +1,273 @@
+mod activity;
+mod csv;
+mod ofx;
+mod qif;
+mod statement;
+
+pub use self::activity::Activity;
+pub use self::csv::write_csv;
+pub use self::ofx::write_ofx;
+pub use self::qif::write_qif;
+pub use self::statement::Statement;
+pub use self::statement::StatementKind;
+
+use chrono::SecondsFormat;
+use std::collections::HashSet;
+use std::io::Write;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::types::ResourceType;
+use crate::types::UtcDate;
+
+///
+/// A column of exported activity.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+  Timestamp,
+  Account,
+  Resource,
+  ResourceId,
+  Type,
+  Status,
+  Amount,
+  Currency,
+  NativeAmount,
+  NativeCurrency,
+  Fee,
+  FeeCurrency,
+  Counterparty,
+  NetworkHash,
+  Description,
+}
+
+impl Column {
+  /// The columns exported by default.
+  pub const DEFAULT: &'static [Column] = &[
+    Column::Timestamp,
+    Column::Type,
+    Column::Status,
+    Column::Amount,
+    Column::Currency,
+    Column::NativeAmount,
+    Column::NativeCurrency,
+    Column::Fee,
+    Column::FeeCurrency,
+    Column::Counterparty,
+    Column::NetworkHash,
+    Column::ResourceId,
+  ];
+
+  /// Returns the header of the column.
+  pub fn name(self) -> &'static str {
+    match self {
+      Column::Timestamp => "timestamp",
+      Column::Account => "account",
+      Column::Resource => "resource",
+      Column::ResourceId => "resource_id",
+      Column::Type => "type",
+      Column::Status => "status",
+      Column::Amount => "amount",
+      Column::Currency => "currency",
+      Column::NativeAmount => "native_amount",
+      Column::NativeCurrency => "native_currency",
+      Column::Fee => "fee",
+      Column::FeeCurrency => "fee_currency",
+      Column::Counterparty => "counterparty",
+      Column::NetworkHash => "network_hash",
+      Column::Description => "description",
+    }
+  }
+
+  /// Returns the value of the column for `activity`.
+  pub fn value(self, activity: &Activity) -> String {
+    fn string<T: ToString>(value: &Option<T>) -> String {
+      value.as_ref().map(ToString::to_string).unwrap_or_default()
+    }
+
+    match self {
+      Column::Timestamp => activity.timestamp.to_rfc3339_opts(SecondsFormat::Secs, true),
+      Column::Account => activity.account.clone(),
+      Column::Resource => crate::name(&activity.resource),
+      Column::ResourceId => activity.id.clone(),
+      Column::Type => activity.kind.clone(),
+      Column::Status => activity.status.clone(),
+      Column::Amount => activity.amount.to_string(),
+      Column::Currency => activity.currency.clone(),
+      Column::NativeAmount => string(&activity.native_amount),
+      Column::NativeCurrency => string(&activity.native_currency),
+      Column::Fee => string(&activity.fee),
+      Column::FeeCurrency => string(&activity.fee_currency),
+      Column::Counterparty => string(&activity.counterparty),
+      Column::NetworkHash => string(&activity.network_hash),
+      Column::Description => string(&activity.description),
+    }
+  }
+}
+
+///
+/// Selects the activity to export and the columns to write.
+///
+/// ```no_run
+/// # use coinbase::prelude::*;
+/// # use coinbase::export::Column;
+/// # use coinbase::export::Export;
+/// # let client = Client::new();
+/// let export = Export::new().columns(&[Column::Timestamp, Column::Type, Column::Amount, Column::Currency]);
+///
+/// client.export_csv(&export, std::io::stdout()).unwrap();
+/// ```
+///
+#[derive(Clone, Debug)]
+pub struct Export {
+  columns: Vec<Column>,
+  resources: Vec<ResourceType>,
+  from: Option<UtcDate>,
+  to: Option<UtcDate>,
+}
+
+impl Default for Export {
+  fn default() -> Self {
+    Self {
+      columns: Column::DEFAULT.to_vec(),
+      resources: vec![
+        ResourceType::Transaction,
+        ResourceType::Buy,
+        ResourceType::Sell,
+        ResourceType::Deposit,
+        ResourceType::Withdrawal,
+      ],
+      from: None,
+      to: None,
+    }
+  }
+}
+
+impl Export {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Sets the columns to write, in order.
+  pub fn columns(mut self, columns: &[Column]) -> Self {
+    self.columns = columns.to_vec();
+    self
+  }
+
+  /// Sets the resources to export (defaults to transactions, buys, sells,
+  /// deposits and withdrawals).
+  pub fn resources(mut self, resources: &[ResourceType]) -> Self {
+    self.resources = resources.to_vec();
+    self
+  }
+
+  /// Only exports activity created at or after `from`.
+  pub fn from(mut self, from: UtcDate) -> Self {
+    self.from = Some(from);
+    self
+  }
+
+  /// Only exports activity created before `to`.
+  pub fn to(mut self, to: UtcDate) -> Self {
+    self.to = Some(to);
+    self
+  }
+
+  /// Returns `true` if `activity` falls within the selection.
+  pub fn includes(&self, activity: &Activity) -> bool {
+    self.resources.contains(&activity.resource)
+      && self.from.is_none_or(|from| activity.timestamp >= from)
+      && self.to.is_none_or(|to| activity.timestamp < to)
+  }
+
+  fn wants(&self, resource: ResourceType) -> bool {
+    self.resources.contains(&resource)
+  }
+}
+
+impl Client {
+  /// Collects the activity of all accounts selected by `export`, sorted by time.
+  ///
+  /// Transactions created by an exported buy or sell are left out, so each
+  /// trade appears once.
+  pub fn activity(&self, export: &Export) -> Result<Vec<Activity>, Error> {
+    let mut activity: Vec<Activity> = Vec::new();
+
+    for account in self.all_accounts()? {
+      let id: &str = &account.meta.id;
+      let mut seen: HashSet<String> = HashSet::new();
+
+      if export.wants(ResourceType::Buy) {
+        for item in self.all_buys(id)? {
+          seen.insert(item.transaction.id.clone());
+          activity.push(Activity::from_buy(id, &item));
+        }
+      }
+
+      if export.wants(ResourceType::Sell) {
+        for item in self.all_sells(id)? {
+          seen.insert(item.transaction.id.clone());
+          activity.push(Activity::from_sell(id, &item));
+        }
+      }
+
+      // Buys and sells are exported from their own resources (which include
+      // fees) rather than from the transactions they created.
+      if export.wants(ResourceType::Transaction) {
+        for item in self.all_transactions(id)? {
+          if !seen.contains(&item.meta.id) {
+            activity.push(Activity::from_transaction(id, &item));
+          }
+        }
+      }
+
+      if export.wants(ResourceType::Deposit) {
+        for item in self.all_deposits(id)? {
+          activity.push(Activity::from_deposit(id, &item));
+        }
+      }
+
+      if export.wants(ResourceType::Withdrawal) {
+        for item in self.all_withdrawals(id)? {
+          activity.push(Activity::from_withdrawal(id, &item));
+        }
+      }
+    }
+
+    activity.retain(|item| export.includes(item));
+    activity.sort_by_key(|item| item.timestamp);
+
+    Ok(activity)
+  }
+
+  /// Collects every account with all of its transactions.
+  pub fn statements(&self) -> Result<Vec<Statement>, Error> {
+    self
+      .all_accounts()?
+      .into_iter()
+      .map(|account| {
+        let transactions = self.all_transactions(&account.meta.id)?;
+
+        Ok(Statement::new(account, transactions))
+      })
+      .collect()
+  }
+
+  /// Writes all accounts as an OFX investment statement.
+  /// Returns the number of transactions written.
+  pub fn export_ofx<W: Write>(&self, writer: W) -> Result<usize, Error> {
+    write_ofx(&self.statements()?, writer)
+  }
+
+  /// Writes all accounts as QIF. Returns the number of transactions written.
+  pub fn export_qif<W: Write>(&self, writer: W) -> Result<usize, Error> {
+    write_qif(&self.statements()?, writer)
+  }
+
+  /// Writes the activity of all accounts selected by `export` as CSV.
+  /// Returns the number of rows written.
+  pub fn export_csv<W: Write>(&self, export: &Export, writer: W) -> Result<usize, Error> {
+    write_csv(&self.activity(export)?, &export.columns, writer)
+  }
+}
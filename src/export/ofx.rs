@@ -0,0 +1,186 @@
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::io::Write;
+
+use crate::error::Error;
+use crate::export::statement::amounts;
+use crate::export::statement::memo;
+use crate::export::statement::unit_price;
+use crate::export::Statement;
+use crate::export::StatementKind;
+use crate::types::Transaction;
+use crate::types::UtcDate;
+
+const BROKER: &str = "coinbase.com";
+
+/// Writes `statements` as an OFX 2.2 investment statement, with fiat accounts
+/// as cash (`INVBANKTRAN`) activity. Returns the number of transactions
+/// written.
+pub fn write_ofx<W: Write>(statements: &[Statement], mut writer: W) -> Result<usize, Error> {
+  let now: String = date(&Utc::now());
+  let mut count: usize = 0;
+
+  writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>"#)?;
+  writeln!(
+    writer,
+    r#"<?OFX OFXHEADER="200" VERSION="220" SECURITY="NONE" OLDFILEUID="NONE" NEWFILEUID="NONE"?>"#
+  )?;
+  writeln!(writer, "<OFX>")?;
+  writeln!(writer, "<SIGNONMSGSRSV1><SONRS>")?;
+  writeln!(writer, "{}", status())?;
+  writeln!(writer, "<DTSERVER>{}</DTSERVER><LANGUAGE>ENG</LANGUAGE>", now)?;
+  writeln!(writer, "</SONRS></SIGNONMSGSRSV1>")?;
+  writeln!(writer, "<INVSTMTMSGSRSV1>")?;
+
+  for (index, statement) in statements.iter().enumerate() {
+    let (start, end): (String, String) = range(&statement.transactions);
+
+    writeln!(writer, "<INVSTMTTRNRS><TRNUID>{}</TRNUID>{}", index + 1, status())?;
+    writeln!(writer, "<INVSTMTRS>")?;
+    writeln!(writer, "<DTASOF>{}</DTASOF>", now)?;
+    writeln!(writer, "<CURDEF>{}</CURDEF>", escape(statement.native_currency()))?;
+    writeln!(
+      writer,
+      "<INVACCTFROM><BROKERID>{}</BROKERID><ACCTID>{}</ACCTID></INVACCTFROM>",
+      BROKER,
+      escape(&statement.account.meta.id)
+    )?;
+    writeln!(
+      writer,
+      "<INVTRANLIST><DTSTART>{}</DTSTART><DTEND>{}</DTEND>",
+      start, end
+    )?;
+
+    for (transaction, kind) in statement.entries() {
+      writeln!(writer, "{}", entry(statement, transaction, kind))?;
+      count += 1;
+    }
+
+    writeln!(writer, "</INVTRANLIST>")?;
+    writeln!(writer, "</INVSTMTRS></INVSTMTTRNRS>")?;
+  }
+
+  writeln!(writer, "</INVSTMTMSGSRSV1>")?;
+  writeln!(writer, "<SECLISTMSGSRSV1><SECLIST>")?;
+
+  for statement in statements.iter().filter(|statement| !statement.is_fiat()) {
+    writeln!(
+      writer,
+      "<OTHERINFO><SECINFO>{}<SECNAME>{}</SECNAME><TICKER>{}</TICKER></SECINFO></OTHERINFO>",
+      secid(&statement.account.balance.currency),
+      escape(&statement.account.currency.name),
+      escape(&statement.account.balance.currency)
+    )?;
+  }
+
+  writeln!(writer, "</SECLIST></SECLISTMSGSRSV1>")?;
+  writeln!(writer, "</OFX>")?;
+
+  writer.flush()?;
+
+  Ok(count)
+}
+
+fn entry(statement: &Statement, transaction: &Transaction, kind: StatementKind) -> String {
+  let (units, native): (Decimal, Decimal) = amounts(transaction);
+  let currency: &str = &statement.account.balance.currency;
+
+  let invtran: String = format!(
+    "<INVTRAN><FITID>{}</FITID><DTTRADE>{}</DTTRADE><MEMO>{}</MEMO></INVTRAN>",
+    escape(&transaction.meta.id),
+    date(&transaction.meta.created()),
+    escape(&memo(transaction))
+  );
+
+  match kind {
+    StatementKind::Buy | StatementKind::Sell => {
+      let (outer, inner, units, total): (&str, &str, Decimal, Decimal) = if kind == StatementKind::Buy {
+        ("BUYOTHER", "INVBUY", units, -native)
+      } else {
+        ("SELLOTHER", "INVSELL", -units, native)
+      };
+
+      format!(
+        "<{outer}><{inner}>{}{}<UNITS>{}</UNITS><UNITPRICE>{}</UNITPRICE><TOTAL>{}</TOTAL>\
+         <SUBACCTSEC>CASH</SUBACCTSEC><SUBACCTFUND>CASH</SUBACCTFUND></{inner}></{outer}>",
+        invtran,
+        secid(currency),
+        units,
+        unit_price(transaction),
+        total,
+        outer = outer,
+        inner = inner,
+      )
+    }
+    StatementKind::TransferIn | StatementKind::TransferOut => {
+      let (action, units): (&str, Decimal) = if kind == StatementKind::TransferIn {
+        ("IN", units)
+      } else {
+        ("OUT", -units)
+      };
+
+      format!(
+        "<TRANSFER>{}{}<SUBACCTSEC>CASH</SUBACCTSEC><UNITS>{}</UNITS><TFERACTION>{}</TFERACTION>\
+         <POSTYPE>LONG</POSTYPE><UNITPRICE>{}</UNITPRICE></TRANSFER>",
+        invtran,
+        secid(currency),
+        units,
+        action,
+        unit_price(transaction)
+      )
+    }
+    StatementKind::Credit | StatementKind::Debit => {
+      let (kind, amount): (&str, Decimal) = if kind == StatementKind::Credit {
+        ("CREDIT", units)
+      } else {
+        ("DEBIT", -units)
+      };
+
+      format!(
+        "<INVBANKTRAN><STMTTRN><TRNTYPE>{}</TRNTYPE><DTPOSTED>{}</DTPOSTED><TRNAMT>{}</TRNAMT>\
+         <FITID>{}</FITID><MEMO>{}</MEMO></STMTTRN><SUBACCTFUND>CASH</SUBACCTFUND></INVBANKTRAN>",
+        kind,
+        date(&transaction.meta.created()),
+        amount,
+        escape(&transaction.meta.id),
+        escape(&memo(transaction))
+      )
+    }
+  }
+}
+
+fn status() -> &'static str {
+  "<STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>"
+}
+
+fn secid(currency: &str) -> String {
+  format!(
+    "<SECID><UNIQUEID>{}</UNIQUEID><UNIQUEIDTYPE>TICKER</UNIQUEIDTYPE></SECID>",
+    escape(currency)
+  )
+}
+
+fn range(transactions: &[Transaction]) -> (String, String) {
+  let dates: Vec<UtcDate> = transactions
+    .iter()
+    .map(|transaction| transaction.meta.created())
+    .collect();
+  let now: UtcDate = Utc::now();
+
+  (
+    date(dates.iter().min().unwrap_or(&now)),
+    date(dates.iter().max().unwrap_or(&now)),
+  )
+}
+
+fn date(date: &UtcDate) -> String {
+  date.format("%Y%m%d%H%M%S.000[0:GMT]").to_string()
+}
+
+fn escape(value: &str) -> String {
+  value
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
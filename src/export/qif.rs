@@ -0,0 +1,78 @@
+use rust_decimal::Decimal;
+use std::io::Write;
+
+use crate::error::Error;
+use crate::export::statement::amounts;
+use crate::export::statement::memo;
+use crate::export::statement::unit_price;
+use crate::export::Statement;
+use crate::export::StatementKind;
+use crate::types::Transaction;
+
+/// Writes `statements` as QIF, with crypto accounts as investment (`Invst`)
+/// accounts and fiat accounts as bank accounts. Returns the number of
+/// transactions written.
+pub fn write_qif<W: Write>(statements: &[Statement], mut writer: W) -> Result<usize, Error> {
+  let mut count: usize = 0;
+
+  for statement in statements {
+    let kind: &str = if statement.is_fiat() { "Bank" } else { "Invst" };
+
+    writeln!(writer, "!Account")?;
+    writeln!(writer, "N{}", line(&statement.account.name))?;
+    writeln!(writer, "T{}", kind)?;
+    writeln!(writer, "^")?;
+    writeln!(writer, "!Type:{}", kind)?;
+
+    for (transaction, kind) in statement.entries() {
+      write!(
+        writer,
+        "{}",
+        entry(&statement.account.balance.currency, transaction, kind)
+      )?;
+      count += 1;
+    }
+  }
+
+  writer.flush()?;
+
+  Ok(count)
+}
+
+fn entry(currency: &str, transaction: &Transaction, kind: StatementKind) -> String {
+  let (units, native): (Decimal, Decimal) = amounts(transaction);
+  let date: String = transaction.meta.created().format("%m/%d/%Y").to_string();
+
+  let mut record: String = format!("D{}\n", date);
+
+  match kind {
+    StatementKind::Credit | StatementKind::Debit => {
+      let amount: Decimal = if kind == StatementKind::Credit { units } else { -units };
+
+      record.push_str(&format!("T{}\n", amount));
+      record.push_str(&format!("N{}\n", line(&transaction.meta.id)));
+    }
+    _ => {
+      let action: &str = match kind {
+        StatementKind::Buy => "Buy",
+        StatementKind::Sell => "Sell",
+        StatementKind::TransferIn => "ShrsIn",
+        _ => "ShrsOut",
+      };
+
+      record.push_str(&format!("N{}\n", action));
+      record.push_str(&format!("Y{}\n", line(currency)));
+      record.push_str(&format!("I{}\n", unit_price(transaction)));
+      record.push_str(&format!("Q{}\n", units));
+      record.push_str(&format!("T{}\n", native));
+    }
+  }
+
+  record.push_str(&format!("M{}\n", line(&memo(transaction))));
+  record.push_str("^\n");
+  record
+}
+
+fn line(value: &str) -> String {
+  value.replace(['\r', '\n'], " ")
+}
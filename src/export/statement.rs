@@ -0,0 +1,110 @@
+use rust_decimal::Decimal;
+
+use crate::types::Account;
+use crate::types::AccountType;
+use crate::types::Transaction;
+use crate::types::TransactionStatus;
+use crate::types::TransactionType;
+
+///
+/// An account and its transactions, as written to OFX and QIF files.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Statement {
+  pub account: Account,
+  pub transactions: Vec<Transaction>,
+}
+
+impl Statement {
+  pub fn new(account: Account, transactions: Vec<Transaction>) -> Self {
+    Self { account, transactions }
+  }
+
+  /// Returns `true` for fiat accounts, which are written as bank accounts.
+  pub fn is_fiat(&self) -> bool {
+    matches!(self.account.kind, AccountType::Fiat)
+  }
+
+  /// Returns the currency that native amounts are reported in.
+  pub fn native_currency(&self) -> &str {
+    self
+      .transactions
+      .iter()
+      .map(|transaction| transaction.native_amount.currency.as_str())
+      .find(|currency| !currency.is_empty())
+      .unwrap_or(&self.account.balance.currency)
+  }
+
+  /// Returns the completed transactions with their statement kind.
+  pub fn entries(&self) -> impl Iterator<Item = (&Transaction, StatementKind)> {
+    let fiat: bool = self.is_fiat();
+
+    self
+      .transactions
+      .iter()
+      .filter(|transaction| matches!(transaction.status, TransactionStatus::Completed))
+      .map(move |transaction| (transaction, StatementKind::of(transaction, fiat)))
+  }
+}
+
+///
+/// How a transaction appears on an investment or bank statement.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementKind {
+  /// Units bought with cash (`Buy`).
+  Buy,
+  /// Units sold for cash (`Sell`).
+  Sell,
+  /// Units received (`Send`, `Request`, `Transfer`, ... with a positive amount).
+  TransferIn,
+  /// Units sent (`Send`, `Transfer`, ... with a negative amount).
+  TransferOut,
+  /// Cash added to a fiat account (`FiatDeposit`, proceeds of a `Sell`, ...).
+  Credit,
+  /// Cash removed from a fiat account (`FiatWithdrawal`, cost of a `Buy`, ...).
+  Debit,
+}
+
+impl StatementKind {
+  /// Maps a transaction of a crypto (`fiat == false`) or fiat account.
+  pub fn of(transaction: &Transaction, fiat: bool) -> Self {
+    let incoming: bool = transaction.amount.amount >= 0.0;
+
+    match (&transaction.kind, fiat) {
+      (_, true) if incoming => StatementKind::Credit,
+      (_, true) => StatementKind::Debit,
+      (TransactionType::Buy, false) => StatementKind::Buy,
+      (TransactionType::Sell, false) => StatementKind::Sell,
+      (_, false) if incoming => StatementKind::TransferIn,
+      (_, false) => StatementKind::TransferOut,
+    }
+  }
+}
+
+/// Returns the absolute amount and native amount of a transaction.
+pub(crate) fn amounts(transaction: &Transaction) -> (Decimal, Decimal) {
+  (
+    crate::decimal(transaction.amount.amount).abs(),
+    crate::decimal(transaction.native_amount.amount).abs(),
+  )
+}
+
+/// Returns the native price of one unit of a transaction.
+pub(crate) fn unit_price(transaction: &Transaction) -> Decimal {
+  let (units, native): (Decimal, Decimal) = amounts(transaction);
+
+  if units.is_zero() {
+    Decimal::ZERO
+  } else {
+    (native / units).round_dp(8).normalize()
+  }
+}
+
+/// Returns the description, or a generated memo naming the transaction type.
+pub(crate) fn memo(transaction: &Transaction) -> String {
+  transaction
+    .description
+    .clone()
+    .unwrap_or_else(|| crate::name(&transaction.kind))
+}
@@ -0,0 +1,216 @@
+use chrono::Duration;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::types::Account;
+use crate::types::Transaction;
+use crate::types::TransactionStatus;
+use crate::types::UtcDate;
+
+///
+/// The balance of an account after a completed transaction.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BalancePoint {
+  pub time: UtcDate,
+  pub transaction: String,
+  /// Signed amount of the transaction.
+  pub change: Decimal,
+  pub balance: Decimal,
+}
+
+///
+/// The closing balance of an account on one day.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct DailyBalance {
+  pub date: NaiveDate,
+  pub balance: Decimal,
+  /// Net change over the day.
+  pub change: Decimal,
+  /// Number of completed transactions on the day.
+  pub transactions: usize,
+}
+
+///
+/// A mismatch between the transaction log and the reported balance.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Discrepancy {
+  /// The balance before the first transaction is not zero: completed
+  /// transactions do not sum to the reported balance by this amount.
+  Unexplained(Decimal),
+  /// The balance is negative after a transaction.
+  Negative { transaction: String, balance: Decimal },
+  /// A completed transaction is not in the account currency and was skipped.
+  Currency { transaction: String, currency: String },
+}
+
+///
+/// A balance timeline rebuilt from an account's transaction log.
+///
+/// The history is replayed backwards from the current `Account.balance`, so
+/// the latest point always matches the reported balance; gaps in the log show
+/// up as a non-zero `opening` balance.
+///
+/// ```
+/// # use coinbase::prelude::*;
+/// # use coinbase::history::BalanceHistory;
+/// # fn run(client: &Client, account: &str) -> Result<(), Error> {
+/// let history: BalanceHistory = client.balance_history(account)?;
+///
+/// for day in history.daily() {
+///   println!("{} {} {}", day.date, day.balance, history.currency);
+/// }
+///
+/// if !history.is_consistent() {
+///   println!("{:?}", history.discrepancies);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct BalanceHistory {
+  pub account: String,
+  pub currency: String,
+  /// The reported (current) balance.
+  pub balance: Decimal,
+  /// The balance before the first completed transaction.
+  pub opening: Decimal,
+  /// Completed transactions, oldest first.
+  pub points: Vec<BalancePoint>,
+  pub discrepancies: Vec<Discrepancy>,
+}
+
+impl BalanceHistory {
+  /// Replays the completed `transactions` of `account` backwards from its
+  /// current balance.
+  pub fn new(account: &Account, transactions: &[Transaction]) -> Self {
+    let currency: &str = &account.balance.currency;
+    let balance: Decimal = crate::decimal(account.balance.amount);
+    let mut discrepancies: Vec<Discrepancy> = Vec::new();
+
+    let mut completed: Vec<&Transaction> = transactions
+      .iter()
+      .filter(|transaction| matches!(transaction.status, TransactionStatus::Completed))
+      .filter(|transaction| {
+        if transaction.amount.currency.eq_ignore_ascii_case(currency) {
+          return true;
+        }
+
+        discrepancies.push(Discrepancy::Currency {
+          transaction: transaction.meta.id.clone(),
+          currency: transaction.amount.currency.clone(),
+        });
+
+        false
+      })
+      .collect();
+
+    completed.sort_by_key(|transaction| transaction.meta.created());
+
+    let mut points: Vec<BalancePoint> = Vec::with_capacity(completed.len());
+    let mut running: Decimal = balance;
+
+    for transaction in completed.into_iter().rev() {
+      let change: Decimal = crate::decimal(transaction.amount.amount);
+
+      points.push(BalancePoint {
+        time: transaction.meta.created(),
+        transaction: transaction.meta.id.clone(),
+        change,
+        balance: running.normalize(),
+      });
+
+      running -= change;
+    }
+
+    points.reverse();
+    running = running.normalize();
+
+    if !running.is_zero() {
+      discrepancies.push(Discrepancy::Unexplained(running));
+    }
+
+    for point in points.iter().filter(|point| point.balance < Decimal::ZERO) {
+      discrepancies.push(Discrepancy::Negative {
+        transaction: point.transaction.clone(),
+        balance: point.balance,
+      });
+    }
+
+    Self {
+      account: account.meta.id.clone(),
+      currency: currency.to_owned(),
+      balance,
+      opening: running,
+      points,
+      discrepancies,
+    }
+  }
+
+  /// Returns `true` if the transaction log fully explains the balance.
+  pub fn is_consistent(&self) -> bool {
+    self.discrepancies.is_empty()
+  }
+
+  /// Returns the balance at `time`.
+  pub fn balance_at(&self, time: UtcDate) -> Decimal {
+    self
+      .points
+      .iter()
+      .take_while(|point| point.time <= time)
+      .last()
+      .map_or(self.opening, |point| point.balance)
+  }
+
+  /// Returns the closing balance of every day from the first to the last
+  /// transaction, including days without activity.
+  pub fn daily(&self) -> Vec<DailyBalance> {
+    let (first, last): (NaiveDate, NaiveDate) = match (self.points.first(), self.points.last()) {
+      (Some(first), Some(last)) => (first.time.date_naive(), last.time.date_naive()),
+      _ => return Vec::new(),
+    };
+
+    let mut days: Vec<DailyBalance> = Vec::new();
+    let mut points = self.points.iter().peekable();
+    let mut balance: Decimal = self.opening;
+    let mut date: NaiveDate = first;
+
+    while date <= last {
+      let mut change: Decimal = Decimal::ZERO;
+      let mut transactions: usize = 0;
+
+      while let Some(point) = points.next_if(|point| point.time.date_naive() == date) {
+        change += point.change;
+        balance = point.balance;
+        transactions += 1;
+      }
+
+      days.push(DailyBalance {
+        date,
+        balance,
+        change,
+        transactions,
+      });
+
+      date += Duration::days(1);
+    }
+
+    days
+  }
+}
+
+impl Client {
+  /// Rebuilds the balance history of `account` from its transactions.
+  pub fn balance_history(&self, account: &str) -> Result<BalanceHistory, Error> {
+    let account: Account = self.get_account(account)?.into_result()?;
+    let transactions: Vec<Transaction> = self.all_transactions(&account.meta.id)?;
+
+    Ok(BalanceHistory::new(&account, &transactions))
+  }
+}
@@ -5,16 +5,48 @@ extern crate serde_derive;
 
 #[macro_use]
 mod macros;
-mod utils;
+mod secret;
+mod trace;
+pub(crate) mod utils;
 
+pub mod alerts;
+pub mod builder;
+pub mod cache;
 pub mod client;
+pub mod config;
+pub mod dca;
+pub mod endpoint;
 pub mod error;
+pub mod export;
+pub mod history;
+pub mod metrics;
+pub mod middleware;
+pub mod portfolio;
+pub mod prices;
+pub mod ratelimit;
+pub mod reconcile;
+pub mod retry;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod sync;
+pub mod tax;
+pub mod transport;
 pub mod types;
+pub mod watch;
 
 use self::utils::*;
 
 pub mod prelude {
+  pub use crate::builder::ClientBuilder;
   pub use crate::client::Client;
+  pub use crate::config::Profile;
   pub use crate::error::Error;
+  pub use crate::history::BalanceHistory;
+  pub use crate::metrics::MetricsSink;
+  pub use crate::middleware::Middleware;
+  pub use crate::portfolio::Portfolio;
+  pub use crate::prices::PriceSeries;
+  pub use crate::retry::RetryPolicy;
+  pub use crate::transport::Transport;
   pub use crate::types::*;
 }
@@ -17,10 +17,27 @@ macro_rules! json {
 }
 
 macro_rules! url {
-  ($path:expr) => {
-    ::reqwest::Url::parse(&[$crate::client::ENDPOINT, $path].join("")).unwrap()
+  ($base:expr, $path:expr) => {
+    $crate::utils::join_url($base, $path)
   };
-  ($path:expr, $params:expr) => {
-    ::reqwest::Url::parse_with_params(&[$crate::client::ENDPOINT, $path].join(""), $params).unwrap()
+}
+
+// Adds the idempotency token accessors to request types with an `idem` field.
+macro_rules! idem {
+  ($ty:ty) => {
+    impl $ty {
+      /// Replaces the generated idempotency token, e.g. with one persisted
+      /// from an earlier attempt. Replaying a request with the same token
+      /// never applies it twice.
+      pub fn with_idem(mut self, idem: &str) -> Self {
+        self.idem = idem.to_owned();
+        self
+      }
+
+      /// Returns the idempotency token of this request.
+      pub fn idem(&self) -> &str {
+        &self.idem
+      }
+    }
   };
 }
@@ -0,0 +1,221 @@
+use reqwest::Method;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::ratelimit::RateLimitStatus;
+
+/// Upper bounds of the latency histogram buckets, in milliseconds.
+pub const LATENCY_BUCKETS: [u64; 10] = [10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+///
+/// Receives metrics for every request sent by a `Client`.
+///
+/// Endpoints are reported as templates (e.g. `accounts/{id}/transactions`),
+/// see `endpoint::template`. All methods default to doing nothing.
+///
+pub trait MetricsSink: Debug + Send + Sync {
+  /// Called for every attempt that received a response (`status`) or failed
+  /// in the transport (`None`).
+  fn request(&self, _endpoint: &str, _method: &Method, _status: Option<u16>, _latency: Duration) {}
+
+  /// Called for every failed attempt with the `ErrorMessage.id` of the API
+  /// error, `status_<code>` for error responses without one, or `transport`.
+  fn error(&self, _endpoint: &str, _id: &str) {}
+
+  /// Called before an attempt is retried by the `RetryPolicy`.
+  fn retry(&self, _endpoint: &str) {}
+
+  /// Called when a request waited for the rate limiter or a `429` response.
+  fn throttle(&self, _endpoint: &str, _wait: Duration) {}
+
+  /// Called with the rate-limit headroom reported by a response.
+  fn rate_limit(&self, _status: &RateLimitStatus) {}
+}
+
+impl<M: MetricsSink + ?Sized> MetricsSink for Arc<M> {
+  fn request(&self, endpoint: &str, method: &Method, status: Option<u16>, latency: Duration) {
+    (**self).request(endpoint, method, status, latency)
+  }
+
+  fn error(&self, endpoint: &str, id: &str) {
+    (**self).error(endpoint, id)
+  }
+
+  fn retry(&self, endpoint: &str) {
+    (**self).retry(endpoint)
+  }
+
+  fn throttle(&self, endpoint: &str, wait: Duration) {
+    (**self).throttle(endpoint, wait)
+  }
+
+  fn rate_limit(&self, status: &RateLimitStatus) {
+    (**self).rate_limit(status)
+  }
+}
+
+///
+/// A `MetricsSink` that discards everything (the default).
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopMetrics;
+
+impl MetricsSink for NoopMetrics {}
+
+///
+/// A latency histogram with the bucket bounds of `LATENCY_BUCKETS`.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Histogram {
+  /// Observation counts per bucket; the last bucket counts everything above
+  /// the largest bound.
+  pub buckets: [u64; 11],
+  pub count: u64,
+  pub sum: Duration,
+}
+
+impl Histogram {
+  pub fn observe(&mut self, value: Duration) {
+    let millis: u128 = value.as_millis();
+    let index: usize = LATENCY_BUCKETS
+      .iter()
+      .position(|bound| millis <= u128::from(*bound))
+      .unwrap_or(LATENCY_BUCKETS.len());
+
+    self.buckets[index] += 1;
+    self.count += 1;
+    self.sum += value;
+  }
+
+  /// Returns the number of observations at or below `millis`, which should be
+  /// one of the bucket bounds.
+  pub fn below(&self, millis: u64) -> u64 {
+    LATENCY_BUCKETS
+      .iter()
+      .zip(self.buckets.iter())
+      .take_while(|(bound, _)| **bound <= millis)
+      .map(|(_, count)| count)
+      .sum()
+  }
+}
+
+///
+/// Metrics collected for a single endpoint template.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EndpointMetrics {
+  pub requests: u64,
+  pub errors: BTreeMap<String, u64>,
+  pub latency: Histogram,
+  pub retries: u64,
+  pub throttled: u64,
+  pub throttle_wait: Duration,
+}
+
+impl EndpointMetrics {
+  /// Returns the total number of errors of all ids.
+  pub fn error_count(&self) -> u64 {
+    self.errors.values().sum()
+  }
+}
+
+///
+/// A `MetricsSink` keeping counters in memory, mostly useful in tests.
+///
+/// ```
+/// # use coinbase::metrics::InMemoryMetrics;
+/// # use coinbase::transport::*;
+/// # use coinbase::prelude::*;
+/// # use std::sync::Arc;
+/// let mock = MockTransport::new();
+/// let metrics = Arc::new(InMemoryMetrics::new());
+///
+/// mock.mock(Method::GET, "time", HttpResponse::json(200, r#"{"data":{"iso":"2019-01-01T00:00:00Z","epoch":0}}"#));
+///
+/// let client = Client::builder().transport(mock).metrics(metrics.clone()).build().unwrap();
+///
+/// client.time().unwrap();
+///
+/// assert_eq!(metrics.endpoint("time").requests, 1);
+/// ```
+///
+#[derive(Debug, Default)]
+pub struct InMemoryMetrics {
+  endpoints: Mutex<BTreeMap<String, EndpointMetrics>>,
+  rate_limit: Mutex<Option<RateLimitStatus>>,
+}
+
+impl InMemoryMetrics {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Returns the metrics of an endpoint template.
+  pub fn endpoint(&self, endpoint: &str) -> EndpointMetrics {
+    self
+      .endpoints
+      .lock()
+      .expect("Poisoned Lock")
+      .get(endpoint)
+      .cloned()
+      .unwrap_or_default()
+  }
+
+  /// Returns the metrics of all endpoints seen so far.
+  pub fn endpoints(&self) -> BTreeMap<String, EndpointMetrics> {
+    self.endpoints.lock().expect("Poisoned Lock").clone()
+  }
+
+  /// Returns the most recently reported rate-limit headroom.
+  pub fn headroom(&self) -> Option<RateLimitStatus> {
+    self.rate_limit.lock().expect("Poisoned Lock").clone()
+  }
+
+  /// Clears all collected metrics.
+  pub fn reset(&self) {
+    self.endpoints.lock().expect("Poisoned Lock").clear();
+    *self.rate_limit.lock().expect("Poisoned Lock") = None;
+  }
+
+  fn update(&self, endpoint: &str, f: impl FnOnce(&mut EndpointMetrics)) {
+    f(self
+      .endpoints
+      .lock()
+      .expect("Poisoned Lock")
+      .entry(endpoint.to_owned())
+      .or_default())
+  }
+}
+
+impl MetricsSink for InMemoryMetrics {
+  fn request(&self, endpoint: &str, _method: &Method, _status: Option<u16>, latency: Duration) {
+    self.update(endpoint, |metrics| {
+      metrics.requests += 1;
+      metrics.latency.observe(latency);
+    })
+  }
+
+  fn error(&self, endpoint: &str, id: &str) {
+    self.update(endpoint, |metrics| {
+      *metrics.errors.entry(id.to_owned()).or_default() += 1
+    })
+  }
+
+  fn retry(&self, endpoint: &str) {
+    self.update(endpoint, |metrics| metrics.retries += 1)
+  }
+
+  fn throttle(&self, endpoint: &str, wait: Duration) {
+    self.update(endpoint, |metrics| {
+      metrics.throttled += 1;
+      metrics.throttle_wait += wait;
+    })
+  }
+
+  fn rate_limit(&self, status: &RateLimitStatus) {
+    *self.rate_limit.lock().expect("Poisoned Lock") = Some(status.clone());
+  }
+}
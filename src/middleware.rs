@@ -0,0 +1,41 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::transport::HttpRequest;
+use crate::transport::HttpResponse;
+
+///
+/// Hooks around every request sent by a `Client`.
+///
+/// Middleware is registered with `ClientBuilder::middleware` and invoked in
+/// registration order for every attempt, including retries.
+///
+pub trait Middleware: Debug + Send + Sync {
+  /// Called with the signed request before it is sent.
+  ///
+  /// Adding headers is safe; changing the method, URL or body invalidates
+  /// the signature.
+  fn before_send(&self, _request: &mut HttpRequest) {}
+
+  /// Called with the response and the time spent waiting for it.
+  fn after_receive(&self, _request: &HttpRequest, _response: &HttpResponse, _latency: Duration) {}
+
+  /// Called when the transport fails to deliver the request.
+  fn on_error(&self, _request: &HttpRequest, _error: &Error, _latency: Duration) {}
+}
+
+impl<M: Middleware + ?Sized> Middleware for Arc<M> {
+  fn before_send(&self, request: &mut HttpRequest) {
+    (**self).before_send(request)
+  }
+
+  fn after_receive(&self, request: &HttpRequest, response: &HttpResponse, latency: Duration) {
+    (**self).after_receive(request, response, latency)
+  }
+
+  fn on_error(&self, request: &HttpRequest, error: &Error, latency: Duration) {
+    (**self).on_error(request, error, latency)
+  }
+}
@@ -0,0 +1,116 @@
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::types::Account;
+use crate::types::Rates;
+use crate::types::User;
+
+///
+/// The balance of one account and its value in the native currency.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Holding {
+  pub account: String,
+  pub name: String,
+  pub currency: String,
+  pub balance: Decimal,
+  /// `None` when no exchange rate to the native currency is known.
+  pub native_value: Option<Decimal>,
+  /// Fraction (0 to 1) of the portfolio total.
+  pub share: Decimal,
+}
+
+///
+/// A valuation of all accounts in the user's native currency.
+///
+/// ```
+/// # use coinbase::prelude::*;
+/// # use coinbase::portfolio::Portfolio;
+/// # fn run(client: &Client) -> Result<(), Error> {
+/// let portfolio: Portfolio = client.portfolio()?;
+///
+/// for holding in portfolio.holdings.iter() {
+///   println!("{} {} = {:?} {}", holding.balance, holding.currency, holding.native_value, portfolio.currency);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Portfolio {
+  pub currency: String,
+  pub holdings: Vec<Holding>,
+  pub total: Decimal,
+}
+
+impl Portfolio {
+  /// Values `accounts` in `currency` using exchange rates quoted in `currency`.
+  pub fn new(currency: &str, accounts: &[Account], rates: &Rates) -> Self {
+    let mut holdings: Vec<Holding> = accounts
+      .iter()
+      .map(|account| {
+        let balance: Decimal = crate::decimal(account.balance.amount);
+        let native_value: Option<Decimal> = convert(balance, &account.balance.currency, currency, rates);
+
+        Holding {
+          account: account.meta.id.clone(),
+          name: account.name.clone(),
+          currency: account.balance.currency.clone(),
+          balance,
+          native_value,
+          share: Decimal::ZERO,
+        }
+      })
+      .collect();
+
+    let total: Decimal = holdings.iter().filter_map(|holding| holding.native_value).sum();
+
+    if !total.is_zero() {
+      for holding in holdings.iter_mut() {
+        holding.share = holding.native_value.map_or(Decimal::ZERO, |value| value / total);
+      }
+    }
+
+    Self {
+      currency: currency.to_owned(),
+      holdings,
+      total,
+    }
+  }
+
+  /// Returns the holdings without a known exchange rate.
+  pub fn unpriced(&self) -> impl Iterator<Item = &Holding> {
+    self.holdings.iter().filter(|holding| holding.native_value.is_none())
+  }
+}
+
+impl Client {
+  /// Values all accounts in the native currency of the current user.
+  pub fn portfolio(&self) -> Result<Portfolio, Error> {
+    let user: User = self.current_user()?.into_result()?;
+    let currency: String = user.native_currency.unwrap_or_else(|| "USD".to_owned());
+    let accounts: Vec<Account> = self.all_accounts()?;
+    let rates: Rates = self.rates(Some(&currency))?.into_result()?;
+
+    Ok(Portfolio::new(&currency, &accounts, &rates))
+  }
+}
+
+fn convert(amount: Decimal, from: &str, to: &str, rates: &Rates) -> Option<Decimal> {
+  if from.eq_ignore_ascii_case(to) {
+    return Some(amount);
+  }
+
+  if !rates.currency.eq_ignore_ascii_case(to) {
+    return None;
+  }
+
+  rates
+    .rates
+    .get(from)
+    .and_then(|rate| Decimal::from_str(rate).ok())
+    .filter(|rate| !rate.is_zero())
+    .map(|rate| amount / rate)
+}
@@ -0,0 +1,227 @@
+use chrono::Duration;
+use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+use std::thread;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::types::Money;
+use crate::types::Rates;
+
+/// A `(base, quote)` currency pair, e.g. `("BTC", "USD")`.
+pub type Pair = (String, String);
+
+/// Results of a batch price request, keyed by pair.
+pub type PriceMap = BTreeMap<Pair, Result<Money, Error>>;
+
+///
+/// The spot price of a currency pair on one day.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PricePoint {
+  pub date: NaiveDate,
+  pub price: f64,
+}
+
+///
+/// Daily spot prices of a currency pair, ordered by date.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct PriceSeries {
+  pub base: String,
+  pub quote: String,
+  pub points: Vec<PricePoint>,
+}
+
+impl PriceSeries {
+  /// Returns the price on `date`, if the series covers it.
+  pub fn get(&self, date: NaiveDate) -> Option<f64> {
+    self
+      .points
+      .binary_search_by_key(&date, |point| point.date)
+      .ok()
+      .map(|index| self.points[index].price)
+  }
+
+  pub fn first(&self) -> Option<&PricePoint> {
+    self.points.first()
+  }
+
+  pub fn last(&self) -> Option<&PricePoint> {
+    self.points.last()
+  }
+
+  pub fn len(&self) -> usize {
+    self.points.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.points.is_empty()
+  }
+}
+
+///
+/// Options of `Client::spot_prices_with`.
+///
+#[derive(Clone, Debug)]
+pub struct SpotPrices {
+  concurrency: usize,
+  from_rates: bool,
+}
+
+impl Default for SpotPrices {
+  fn default() -> Self {
+    Self {
+      concurrency: 4,
+      from_rates: false,
+    }
+  }
+}
+
+impl SpotPrices {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Sets the maximum number of requests in flight (defaults to 4).
+  pub fn concurrency(mut self, concurrency: usize) -> Self {
+    self.concurrency = concurrency.max(1);
+    self
+  }
+
+  /// Derives the prices of pairs sharing the most common quote currency from
+  /// a single `rates()` call instead of fetching them one by one.
+  ///
+  /// Exchange rates are mid-market and may differ slightly from spot prices.
+  pub fn from_rates(mut self, from_rates: bool) -> Self {
+    self.from_rates = from_rates;
+    self
+  }
+}
+
+impl Client {
+  /// Get the current market price of many currency pairs.
+  ///
+  /// Pairs are de-duplicated (case-insensitively) and fetched concurrently;
+  /// each pair maps to its own result.
+  pub fn spot_prices(&self, pairs: &[(&str, &str)]) -> PriceMap {
+    self.spot_prices_with(pairs, &SpotPrices::new())
+  }
+
+  /// Get the current market price of many currency pairs with custom options.
+  pub fn spot_prices_with(&self, pairs: &[(&str, &str)], options: &SpotPrices) -> PriceMap {
+    let mut pending: BTreeSet<Pair> = pairs
+      .iter()
+      .map(|(base, quote)| (base.to_uppercase(), quote.to_uppercase()))
+      .collect();
+
+    let mut prices: PriceMap = PriceMap::new();
+
+    if options.from_rates {
+      if let Some(quote) = common_quote(&pending) {
+        if let Ok(rates) = self.rates(Some(&quote)).and_then(|response| response.into_result()) {
+          pending.retain(|pair| match derive(&rates, pair) {
+            Some(money) => {
+              prices.insert(pair.clone(), Ok(money));
+              false
+            }
+            None => true,
+          });
+        }
+      }
+    }
+
+    let queue: Mutex<Vec<Pair>> = Mutex::new(pending.into_iter().rev().collect());
+    let results: Mutex<PriceMap> = Mutex::new(prices);
+    let workers: usize = options.concurrency.min(queue.lock().expect("Poisoned Lock").len());
+
+    thread::scope(|scope| {
+      for _ in 0..workers {
+        scope.spawn(|| loop {
+          let pair: Pair = match queue.lock().expect("Poisoned Lock").pop() {
+            Some(pair) => pair,
+            None => break,
+          };
+
+          let result: Result<Money, Error> = self
+            .spot_price(&pair.0, &pair.1)
+            .and_then(|response| response.into_result());
+
+          results.lock().expect("Poisoned Lock").insert(pair, result);
+        });
+      }
+    });
+
+    results.into_inner().expect("Poisoned Lock")
+  }
+
+  /// Get the daily spot price of a currency pair from `from` to `to` (inclusive).
+  ///
+  /// Days are fetched one at a time, so requests go through the client's
+  /// rate limiter and cache.
+  pub fn spot_price_history(
+    &self,
+    base: &str,
+    quote: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+  ) -> Result<PriceSeries, Error> {
+    let base: String = base.to_uppercase();
+    let quote: String = quote.to_uppercase();
+    let mut points: Vec<PricePoint> = Vec::new();
+    let mut date: NaiveDate = from;
+
+    while date <= to {
+      let price: Money = self.historic_spot_price(&base, &quote, date)?.into_result()?;
+
+      points.push(PricePoint {
+        date,
+        price: price.amount,
+      });
+
+      date += Duration::days(1);
+    }
+
+    Ok(PriceSeries { base, quote, points })
+  }
+}
+
+fn common_quote(pairs: &BTreeSet<Pair>) -> Option<String> {
+  let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+
+  for (_, quote) in pairs {
+    *counts.entry(quote).or_default() += 1;
+  }
+
+  counts
+    .into_iter()
+    .max_by_key(|(_, count)| *count)
+    .map(|(quote, _)| quote.to_owned())
+}
+
+fn derive(rates: &Rates, (base, quote): &Pair) -> Option<Money> {
+  let rate = |currency: &str| -> Option<f64> {
+    rates
+      .rates
+      .get(currency)
+      .and_then(|rate| rate.parse::<f64>().ok())
+      .filter(|rate| *rate > 0.0)
+  };
+
+  let amount: f64 = if rates.currency.eq_ignore_ascii_case(quote) {
+    1.0 / rate(base)?
+  } else if rates.currency.eq_ignore_ascii_case(base) {
+    rate(quote)?
+  } else {
+    return None;
+  };
+
+  Some(Money {
+    amount,
+    currency: quote.to_owned(),
+    base: Some(base.to_owned()),
+    scale: None,
+  })
+}
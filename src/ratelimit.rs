@@ -0,0 +1,141 @@
+use chrono::DateTime;
+use chrono::FixedOffset;
+use chrono::Utc;
+use reqwest::header::HeaderMap;
+use reqwest::header::RETRY_AFTER;
+use std::sync::Mutex;
+use std::thread::sleep;
+use std::time::Duration;
+use std::time::Instant;
+
+const LIMIT: &[&str] = &["cb-ratelimit-limit", "x-ratelimit-limit", "ratelimit-limit"];
+const REMAINING: &[&str] = &["cb-ratelimit-remaining", "x-ratelimit-remaining", "ratelimit-remaining"];
+const RESET: &[&str] = &["cb-ratelimit-reset", "x-ratelimit-reset", "ratelimit-reset"];
+
+#[derive(Debug)]
+struct Bucket {
+  tokens: f64,
+  updated: Instant,
+}
+
+///
+/// A token-bucket limiter allowing bursts of up to `requests` requests,
+/// refilled evenly over `per`.
+///
+#[derive(Debug)]
+pub struct RateLimiter {
+  capacity: f64,
+  rate: f64,
+  bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+  pub fn new(requests: u32, per: Duration) -> Self {
+    let capacity: f64 = f64::from(requests.max(1));
+
+    Self {
+      capacity,
+      rate: capacity / per.as_secs_f64().max(f64::EPSILON),
+      bucket: Mutex::new(Bucket {
+        tokens: capacity,
+        updated: Instant::now(),
+      }),
+    }
+  }
+
+  /// Takes a token, blocking until one is available. Returns the time spent waiting.
+  pub fn acquire(&self) -> Duration {
+    let wait: Duration = self.reserve();
+
+    if wait > Duration::from_secs(0) {
+      sleep(wait);
+    }
+
+    wait
+  }
+
+  /// Takes a token if one is available without waiting.
+  pub fn try_acquire(&self) -> bool {
+    let mut bucket = self.bucket.lock().expect("Poisoned Lock");
+
+    self.refill(&mut bucket);
+
+    if bucket.tokens >= 1.0 {
+      bucket.tokens -= 1.0;
+      true
+    } else {
+      false
+    }
+  }
+
+  fn reserve(&self) -> Duration {
+    let mut bucket = self.bucket.lock().expect("Poisoned Lock");
+
+    self.refill(&mut bucket);
+
+    bucket.tokens -= 1.0;
+
+    if bucket.tokens >= 0.0 {
+      Duration::from_secs(0)
+    } else {
+      Duration::from_secs_f64(-bucket.tokens / self.rate)
+    }
+  }
+
+  fn refill(&self, bucket: &mut Bucket) {
+    let now: Instant = Instant::now();
+    let elapsed: f64 = now.duration_since(bucket.updated).as_secs_f64();
+
+    bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+    bucket.updated = now;
+  }
+}
+
+///
+/// Rate-limit information reported by the API in response headers.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RateLimitStatus {
+  pub limit: Option<u64>,
+  pub remaining: Option<u64>,
+  pub reset: Option<u64>,
+}
+
+impl RateLimitStatus {
+  /// Parses `CB-RateLimit-*`, `X-RateLimit-*` or `RateLimit-*` headers.
+  pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+    let status: Self = Self {
+      limit: header(headers, LIMIT),
+      remaining: header(headers, REMAINING),
+      reset: header(headers, RESET),
+    };
+
+    if status == Self::default() {
+      None
+    } else {
+      Some(status)
+    }
+  }
+}
+
+/// Returns the delay requested by a `Retry-After` header, given either in
+/// seconds or as an HTTP date (dates in the past mean no delay).
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+  let value: &str = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+  if let Ok(seconds) = value.parse() {
+    return Some(Duration::from_secs(seconds));
+  }
+
+  let date: DateTime<FixedOffset> = DateTime::parse_from_rfc2822(value).ok()?;
+
+  Some((date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default())
+}
+
+fn header(headers: &HeaderMap, names: &[&str]) -> Option<u64> {
+  names
+    .iter()
+    .filter_map(|name| headers.get(*name))
+    .filter_map(|value| value.to_str().ok())
+    .find_map(|value| value.trim().parse().ok())
+}
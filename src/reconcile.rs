@@ -0,0 +1,370 @@
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::name;
+use crate::types::Buy;
+use crate::types::Deposit;
+use crate::types::Money;
+use crate::types::ResourceRef;
+use crate::types::ResourceType;
+use crate::types::Sell;
+use crate::types::Transaction;
+use crate::types::TransactionStatus;
+use crate::types::TransactionType;
+use crate::types::TransferStatus;
+use crate::types::Withdrawal;
+
+///
+/// A disagreement between a buy, sell, deposit or withdrawal and the
+/// transaction it references.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Issue {
+  /// The record is completed but does not reference a transaction.
+  Unlinked,
+  /// The referenced transaction was not found in the account.
+  MissingTransaction,
+  /// The transaction is also referenced by another record.
+  Duplicate { other: String },
+  /// The transaction type does not match the resource.
+  Kind { actual: String },
+  /// The transfer and transaction statuses do not agree.
+  Status { expected: String, actual: String },
+  /// The signed amounts differ.
+  Amount { expected: Decimal, actual: Decimal },
+  /// The amounts are in different currencies.
+  Currency { expected: String, actual: String },
+}
+
+///
+/// A record that failed reconciliation, with every issue found.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Mismatch {
+  pub account: String,
+  pub resource: ResourceType,
+  pub id: String,
+  /// Id of the referenced transaction, if any.
+  pub transaction: Option<String>,
+  pub issues: Vec<Issue>,
+}
+
+///
+/// A buy, sell or fiat transaction that no record references.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Orphan {
+  pub account: String,
+  pub transaction: String,
+  pub kind: String,
+  pub status: String,
+}
+
+///
+/// The result of reconciling records against transactions.
+///
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct Report {
+  /// Number of records that agree with their transaction.
+  pub matched: usize,
+  /// Number of uncommitted or canceled records without a transaction.
+  pub unlinked: usize,
+  pub mismatches: Vec<Mismatch>,
+  pub orphans: Vec<Orphan>,
+}
+
+impl Report {
+  /// Returns `true` if no mismatches or orphans were found.
+  pub fn is_clean(&self) -> bool {
+    self.mismatches.is_empty() && self.orphans.is_empty()
+  }
+}
+
+///
+/// Joins buys, sells, deposits and withdrawals with the transactions they
+/// reference and checks that kinds, statuses and amounts agree.
+///
+/// ```
+/// # use coinbase::prelude::*;
+/// # use coinbase::reconcile::Report;
+/// # fn run(client: &Client) -> Result<(), Error> {
+/// let report: Report = client.reconcile_all()?;
+///
+/// for mismatch in report.mismatches.iter() {
+///   println!("{:?} {}: {:?}", mismatch.resource, mismatch.id, mismatch.issues);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Debug, Default)]
+pub struct Reconciler {
+  records: Vec<Record>,
+  transactions: Vec<(String, Transaction)>,
+}
+
+impl Reconciler {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds the buys of `account`.
+  pub fn buys(&mut self, account: &str, buys: &[Buy]) -> &mut Self {
+    self.add(account, ResourceType::Buy, Decimal::ONE, buys)
+  }
+
+  /// Adds the sells of `account`.
+  pub fn sells(&mut self, account: &str, sells: &[Sell]) -> &mut Self {
+    self.add(account, ResourceType::Sell, Decimal::NEGATIVE_ONE, sells)
+  }
+
+  /// Adds the deposits of `account`.
+  pub fn deposits(&mut self, account: &str, deposits: &[Deposit]) -> &mut Self {
+    self.add(account, ResourceType::Deposit, Decimal::ONE, deposits)
+  }
+
+  /// Adds the withdrawals of `account`.
+  pub fn withdrawals(&mut self, account: &str, withdrawals: &[Withdrawal]) -> &mut Self {
+    self.add(account, ResourceType::Withdrawal, Decimal::NEGATIVE_ONE, withdrawals)
+  }
+
+  /// Adds the transactions of `account`.
+  pub fn transactions(&mut self, account: &str, transactions: &[Transaction]) -> &mut Self {
+    self.transactions.extend(
+      transactions
+        .iter()
+        .map(|transaction| (account.to_owned(), transaction.clone())),
+    );
+
+    self
+  }
+
+  /// Reconciles every record added so far.
+  pub fn report(&self) -> Report {
+    let transactions: HashMap<(&str, &str), &Transaction> = self
+      .transactions
+      .iter()
+      .map(|(account, transaction)| ((account.as_str(), transaction.meta.id.as_str()), transaction))
+      .collect();
+
+    let mut report: Report = Report::default();
+    let mut linked: HashMap<(&str, &str), &str> = HashMap::new();
+
+    for record in self.records.iter() {
+      let reference: &str = &record.transaction;
+
+      if reference.is_empty() {
+        if matches!(record.status, TransferStatus::Completed) {
+          report.mismatches.push(record.mismatch(vec![Issue::Unlinked]));
+        } else {
+          report.unlinked += 1;
+        }
+
+        continue;
+      }
+
+      let key: (&str, &str) = (record.account.as_str(), reference);
+      let mut issues: Vec<Issue> = Vec::new();
+
+      if let Some(other) = linked.insert(key, &record.id) {
+        issues.push(Issue::Duplicate {
+          other: other.to_owned(),
+        });
+      }
+
+      match transactions.get(&key) {
+        Some(transaction) => issues.extend(record.compare(transaction)),
+        None => issues.push(Issue::MissingTransaction),
+      }
+
+      if issues.is_empty() {
+        report.matched += 1;
+      } else {
+        report.mismatches.push(record.mismatch(issues));
+      }
+    }
+
+    report.orphans = self
+      .transactions
+      .iter()
+      .filter(|(_, transaction)| expects_record(&transaction.kind))
+      .filter(|(account, transaction)| !linked.contains_key(&(account.as_str(), transaction.meta.id.as_str())))
+      .map(|(account, transaction)| Orphan {
+        account: account.clone(),
+        transaction: transaction.meta.id.clone(),
+        kind: name(&transaction.kind),
+        status: name(&transaction.status),
+      })
+      .collect();
+
+    report
+  }
+
+  fn add<T: Transfer>(&mut self, account: &str, resource: ResourceType, sign: Decimal, items: &[T]) -> &mut Self {
+    for item in items {
+      let (id, status, transaction, amount): (&str, &TransferStatus, &ResourceRef, &Money) = item.parts();
+
+      self.records.push(Record {
+        account: account.to_owned(),
+        resource: resource.clone(),
+        id: id.to_owned(),
+        status: status.clone(),
+        transaction: transaction.id.clone(),
+        amount: crate::decimal(amount.amount).abs() * sign,
+        currency: amount.currency.clone(),
+      });
+    }
+
+    self
+  }
+}
+
+impl Client {
+  /// Reconciles the buys, sells, deposits and withdrawals of `account`
+  /// against its transactions.
+  pub fn reconcile(&self, account: &str) -> Result<Report, Error> {
+    let mut reconciler: Reconciler = Reconciler::new();
+
+    self.collect(&mut reconciler, account)?;
+
+    Ok(reconciler.report())
+  }
+
+  /// Reconciles all accounts of the current user.
+  pub fn reconcile_all(&self) -> Result<Report, Error> {
+    let mut reconciler: Reconciler = Reconciler::new();
+
+    for account in self.all_accounts()? {
+      self.collect(&mut reconciler, &account.meta.id)?;
+    }
+
+    Ok(reconciler.report())
+  }
+
+  fn collect(&self, reconciler: &mut Reconciler, account: &str) -> Result<(), Error> {
+    reconciler
+      .buys(account, &self.all_buys(account)?)
+      .sells(account, &self.all_sells(account)?)
+      .deposits(account, &self.all_deposits(account)?)
+      .withdrawals(account, &self.all_withdrawals(account)?)
+      .transactions(account, &self.all_transactions(account)?);
+
+    Ok(())
+  }
+}
+
+/// The fields shared by buys, sells, deposits and withdrawals.
+trait Transfer {
+  fn parts(&self) -> (&str, &TransferStatus, &ResourceRef, &Money);
+}
+
+macro_rules! transfer {
+  ($($kind:ty),*) => {
+    $(
+      impl Transfer for $kind {
+        fn parts(&self) -> (&str, &TransferStatus, &ResourceRef, &Money) {
+          (&self.meta.id, &self.status, &self.transaction, &self.amount)
+        }
+      }
+    )*
+  };
+}
+
+transfer!(Buy, Sell, Deposit, Withdrawal);
+
+#[derive(Clone, Debug)]
+struct Record {
+  account: String,
+  resource: ResourceType,
+  id: String,
+  status: TransferStatus,
+  transaction: String,
+  /// Expected signed amount of the transaction.
+  amount: Decimal,
+  currency: String,
+}
+
+impl Record {
+  fn compare(&self, transaction: &Transaction) -> Vec<Issue> {
+    let mut issues: Vec<Issue> = Vec::new();
+
+    if !kind_agrees(&self.resource, &transaction.kind) {
+      issues.push(Issue::Kind {
+        actual: name(&transaction.kind),
+      });
+    }
+
+    if !status_agrees(&self.status, &transaction.status) {
+      issues.push(Issue::Status {
+        expected: name(&self.status),
+        actual: name(&transaction.status),
+      });
+    }
+
+    if !self.currency.eq_ignore_ascii_case(&transaction.amount.currency) {
+      issues.push(Issue::Currency {
+        expected: self.currency.clone(),
+        actual: transaction.amount.currency.clone(),
+      });
+    }
+
+    let actual: Decimal = crate::decimal(transaction.amount.amount);
+
+    if actual != self.amount {
+      issues.push(Issue::Amount {
+        expected: self.amount,
+        actual,
+      });
+    }
+
+    issues
+  }
+
+  fn mismatch(&self, issues: Vec<Issue>) -> Mismatch {
+    Mismatch {
+      account: self.account.clone(),
+      resource: self.resource.clone(),
+      id: self.id.clone(),
+      transaction: Some(self.transaction.clone()).filter(|id| !id.is_empty()),
+      issues,
+    }
+  }
+}
+
+fn kind_agrees(resource: &ResourceType, kind: &TransactionType) -> bool {
+  match resource {
+    ResourceType::Buy => matches!(kind, TransactionType::Buy),
+    ResourceType::Sell => matches!(kind, TransactionType::Sell),
+    ResourceType::Deposit => matches!(kind, TransactionType::FiatDeposit | TransactionType::ExchangeDeposit),
+    ResourceType::Withdrawal => matches!(
+      kind,
+      TransactionType::FiatWithdrawal | TransactionType::ExchangeWithdrawal | TransactionType::VaultWithdrawal
+    ),
+    _ => false,
+  }
+}
+
+fn status_agrees(transfer: &TransferStatus, transaction: &TransactionStatus) -> bool {
+  match transfer {
+    TransferStatus::Completed => matches!(transaction, TransactionStatus::Completed),
+    TransferStatus::Created => matches!(
+      transaction,
+      TransactionStatus::Pending | TransactionStatus::WaitingForSignature | TransactionStatus::WaitingForClearing
+    ),
+    TransferStatus::Canceled => matches!(
+      transaction,
+      TransactionStatus::Canceled | TransactionStatus::Failed | TransactionStatus::Expired
+    ),
+    TransferStatus::Unknown => false,
+  }
+}
+
+fn expects_record(kind: &TransactionType) -> bool {
+  matches!(
+    kind,
+    TransactionType::Buy | TransactionType::Sell | TransactionType::FiatDeposit | TransactionType::FiatWithdrawal
+  )
+}
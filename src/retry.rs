@@ -0,0 +1,116 @@
+use reqwest::Method;
+use serde_json::from_str;
+use serde_json::Value;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::hash::Hasher;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::transport::HttpResponse;
+use crate::types::ErrorMessage;
+
+#[derive(Default, Deserialize)]
+struct Errors {
+  #[serde(default)]
+  errors: Vec<ErrorMessage>,
+}
+
+///
+/// Controls how failed requests are retried.
+///
+/// Retries only apply to `GET` requests and to `POST` requests whose JSON
+/// body carries an `idem` key; other requests are never retried. Delays grow
+/// exponentially from `base_delay` up to `max_delay`, with full jitter if
+/// enabled.
+///
+/// Clients never retry unless a policy is set with
+/// `ClientBuilder::retry_policy`; the default policy makes up to three
+/// attempts on server errors and transport errors.
+///
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+  /// Total number of attempts, including the first one.
+  pub max_attempts: u32,
+  pub base_delay: Duration,
+  pub max_delay: Duration,
+  pub jitter: bool,
+  /// HTTP status codes that are retried.
+  pub statuses: Vec<u16>,
+  /// `ErrorMessage` ids that are retried, regardless of the status code.
+  pub error_ids: Vec<String>,
+  /// Whether connection errors and timeouts are retried.
+  pub transport_errors: bool,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self {
+      max_attempts: 3,
+      base_delay: Duration::from_millis(250),
+      max_delay: Duration::from_secs(10),
+      jitter: true,
+      statuses: vec![500, 502, 503, 504],
+      error_ids: vec!["internal_server_error".into()],
+      transport_errors: true,
+    }
+  }
+}
+
+impl RetryPolicy {
+  /// A policy that never retries.
+  pub fn none() -> Self {
+    Self {
+      max_attempts: 1,
+      ..Default::default()
+    }
+  }
+
+  /// Returns `true` if requests with the given `method` and `body` may be retried.
+  pub fn applies(&self, method: &Method, body: &str) -> bool {
+    match *method {
+      Method::GET => true,
+      Method::POST => from_str::<Value>(body)
+        .ok()
+        .and_then(|body| body.get("idem").map(|idem| !idem.is_null()))
+        .unwrap_or(false),
+      _ => false,
+    }
+  }
+
+  /// Returns the delay before retry number `attempt` (starting at 1).
+  pub fn delay(&self, attempt: u32) -> Duration {
+    let factor: u32 = 1 << attempt.saturating_sub(1).min(16);
+    let delay: Duration = self.base_delay.checked_mul(factor).unwrap_or(self.max_delay);
+    let delay: Duration = delay.min(self.max_delay);
+
+    if self.jitter {
+      delay.mul_f64(random())
+    } else {
+      delay
+    }
+  }
+
+  pub(crate) fn retry_response(&self, response: &HttpResponse) -> bool {
+    if self.statuses.contains(&response.status.as_u16()) {
+      return true;
+    }
+
+    from_str::<Errors>(&response.body)
+      .unwrap_or_default()
+      .errors
+      .iter()
+      .any(|error| self.error_ids.contains(&error.id))
+  }
+
+  pub(crate) fn retry_error(&self, error: &Error) -> bool {
+    self.transport_errors && matches!(error, Error::HTTP(_) | Error::IO(_))
+  }
+}
+
+// A random value in `[0, 1)`, seeded per call from the std hasher keys.
+fn random() -> f64 {
+  let value: u64 = RandomState::new().build_hasher().finish();
+
+  (value >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,42 @@
+use serde::Deserialize;
+use serde::Deserializer;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use zeroize::Zeroize;
+
+/// A credential that is never printed and is wiped from memory on drop.
+#[derive(Default)]
+pub(crate) struct Secret(String);
+
+impl Secret {
+  pub(crate) fn new(value: &str) -> Self {
+    Secret(value.to_owned())
+  }
+
+  pub(crate) fn expose(&self) -> &str {
+    &self.0
+  }
+
+  pub(crate) fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
+impl Drop for Secret {
+  fn drop(&mut self) {
+    self.0.zeroize();
+  }
+}
+
+impl Debug for Secret {
+  fn fmt(&self, f: &mut Formatter) -> Result {
+    f.write_str("[REDACTED]")
+  }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+    String::deserialize(deserializer).map(Secret)
+  }
+}
@@ -0,0 +1,267 @@
+//!
+//! A local HTTP server emulating the v2 wallet API for offline tests.
+//!
+//! Requires the `server` feature.
+//!
+mod routes;
+mod state;
+
+pub use self::state::money;
+pub use self::state::State;
+
+use reqwest::Url;
+use serde_json::from_str;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result as FResult;
+use std::io::Error as IO;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::thread::spawn;
+use std::thread::JoinHandle;
+use tiny_http::Header;
+use tiny_http::Request as HttpRequest;
+use tiny_http::Response as HttpResponse;
+use tiny_http::Server;
+
+use crate::builder::ClientBuilder;
+use crate::client::Client;
+use crate::error::Error;
+use crate::secret::Secret;
+use crate::server::routes::route;
+use crate::server::routes::ApiError;
+use crate::server::routes::Reply;
+use crate::server::routes::Request;
+
+const PUBLIC: &[&str] = &["time", "currencies", "exchange-rates", "prices"];
+const MAX_SKEW: i64 = 30;
+
+#[derive(Debug)]
+struct Credentials {
+  key: String,
+  secret: Secret,
+}
+
+///
+/// A local HTTP server emulating the Coinbase v2 wallet API.
+///
+/// Serves accounts, transactions, buys, sells, deposits, withdrawals,
+/// prices and exchange rates from an in-memory `State` with cursor-based
+/// pagination and Coinbase-style error envelopes. When started with
+/// credentials, private endpoints require valid `CB-ACCESS-*` signatures.
+///
+/// The server shuts down when dropped.
+///
+/// ```
+/// # use coinbase::server::MockServer;
+/// let server = MockServer::start().unwrap();
+/// let client = server.client();
+///
+/// assert_eq!(client.spot_price("BTC", "USD").unwrap().data.amount, 10000.0);
+/// ```
+///
+pub struct MockServer {
+  server: Arc<Server>,
+  state: Arc<Mutex<State>>,
+  credentials: Arc<Option<Credentials>>,
+  thread: Option<JoinHandle<()>>,
+  base_url: String,
+}
+
+impl MockServer {
+  /// Starts a server with the default seed data that accepts unsigned requests.
+  pub fn start() -> Result<Self, Error> {
+    Self::start_with(State::default(), None)
+  }
+
+  /// Starts a server with the given `state`, verifying request signatures
+  /// against `credentials` (key, secret) if provided.
+  pub fn start_with(state: State, credentials: Option<(&str, &str)>) -> Result<Self, Error> {
+    let server: Arc<Server> = Server::http("127.0.0.1:0")
+      .map(Arc::new)
+      .map_err(|error| IO::other(error.to_string()))?;
+
+    let port: u16 = server
+      .server_addr()
+      .to_ip()
+      .map(|addr| addr.port())
+      .ok_or_else(|| IO::other("Invalid server address"))?;
+
+    let credentials: Arc<Option<Credentials>> = Arc::new(credentials.map(|(key, secret)| Credentials {
+      key: key.to_owned(),
+      secret: Secret::new(secret),
+    }));
+
+    let state: Arc<Mutex<State>> = Arc::new(Mutex::new(state));
+
+    let thread: JoinHandle<()> = {
+      let server: Arc<Server> = server.clone();
+      let state: Arc<Mutex<State>> = state.clone();
+      let credentials: Arc<Option<Credentials>> = credentials.clone();
+
+      spawn(move || {
+        for request in server.incoming_requests() {
+          handle(request, &state, &credentials);
+        }
+      })
+    };
+
+    Ok(Self {
+      server,
+      state,
+      credentials,
+      thread: Some(thread),
+      base_url: format!("http://127.0.0.1:{}/v2/", port),
+    })
+  }
+
+  /// Returns the base URL of the server (`http://127.0.0.1:<port>/v2/`).
+  pub fn base_url(&self) -> &str {
+    &self.base_url
+  }
+
+  /// Returns a `ClientBuilder` pointed at this server, with the server
+  /// credentials (if any).
+  pub fn builder(&self) -> ClientBuilder {
+    let builder: ClientBuilder = Client::builder().base_url(&self.base_url);
+
+    match &*self.credentials {
+      Some(credentials) => builder.credentials(&credentials.key, credentials.secret.expose()),
+      None => builder,
+    }
+  }
+
+  /// Returns a `Client` pointed at this server.
+  pub fn client(&self) -> Client {
+    self.builder().build().expect("Invalid Client")
+  }
+
+  /// Locks and returns the server state.
+  pub fn state(&self) -> MutexGuard<'_, State> {
+    self.state.lock().expect("Poisoned Lock")
+  }
+}
+
+impl Debug for MockServer {
+  fn fmt(&self, f: &mut Formatter) -> FResult {
+    f.debug_struct("MockServer").field("base_url", &self.base_url).finish()
+  }
+}
+
+impl Drop for MockServer {
+  fn drop(&mut self) {
+    self.server.unblock();
+
+    if let Some(thread) = self.thread.take() {
+      let _ = thread.join();
+    }
+  }
+}
+
+fn handle(mut request: HttpRequest, state: &Mutex<State>, credentials: &Option<Credentials>) {
+  let mut body: String = String::new();
+
+  let reply: Reply = match request.as_reader().read_to_string(&mut body) {
+    Ok(_) => dispatch(&request, &body, state, credentials),
+    Err(_) => Err(ApiError::new(400, "invalid_request", "Invalid body")),
+  };
+
+  let (status, body): (u16, String) = reply.unwrap_or_else(|error| (error.status, error.body()));
+  let header: Header = Header::from_bytes("Content-Type", "application/json").expect("Invalid Header");
+
+  let _ = request.respond(
+    HttpResponse::from_string(body)
+      .with_status_code(status)
+      .with_header(header),
+  );
+}
+
+fn dispatch(request: &HttpRequest, body: &str, state: &Mutex<State>, credentials: &Option<Credentials>) -> Reply {
+  let not_found = || ApiError::new(404, "not_found", "Not found");
+
+  let url: Url = Url::parse(&format!("http://localhost{}", request.url())).map_err(|_| not_found())?;
+
+  let segments: Vec<String> = url
+    .path_segments()
+    .map(|segments| {
+      segments
+        .filter(|segment| !segment.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+    })
+    .unwrap_or_default();
+
+  if segments.first().map(String::as_str) != Some("v2") {
+    return Err(not_found());
+  }
+
+  if !PUBLIC.contains(&segments.get(1).map(String::as_str).unwrap_or_default()) {
+    if let Some(credentials) = credentials {
+      authenticate(request, body, credentials)?;
+    }
+  }
+
+  let query: HashMap<String, String> = url.query_pairs().into_owned().collect();
+
+  let body: Value = if body.trim().is_empty() {
+    Value::Null
+  } else {
+    from_str(body).map_err(|_| ApiError::new(400, "invalid_request", "Invalid JSON"))?
+  };
+
+  let method: String = request.method().to_string().to_uppercase();
+
+  let request: Request = Request {
+    method: &method,
+    segments: segments.iter().skip(1).map(String::as_str).collect(),
+    query,
+    body,
+  };
+
+  route(&mut state.lock().expect("Poisoned Lock"), &request)
+}
+
+fn authenticate(request: &HttpRequest, body: &str, credentials: &Credentials) -> Result<(), ApiError> {
+  let header = |name: &str| {
+    request
+      .headers()
+      .iter()
+      .find(|header| header.field.as_str().as_str().eq_ignore_ascii_case(name))
+      .map(|header| header.value.as_str().to_owned())
+  };
+
+  let error = |message: &str| ApiError::new(401, "authentication_error", message);
+
+  let key: String = header("CB-ACCESS-KEY").ok_or_else(|| error("Missing CB-ACCESS-KEY"))?;
+  let sign: String = header("CB-ACCESS-SIGN").ok_or_else(|| error("Missing CB-ACCESS-SIGN"))?;
+  let timestamp: String = header("CB-ACCESS-TIMESTAMP").ok_or_else(|| error("Missing CB-ACCESS-TIMESTAMP"))?;
+
+  if key != credentials.key {
+    return Err(error("Invalid API key"));
+  }
+
+  let time: i64 = timestamp.parse().map_err(|_| error("Invalid timestamp"))?;
+
+  if (crate::timestamp() - time).abs() > MAX_SKEW {
+    return Err(error("Expired timestamp"));
+  }
+
+  let message: String = format!(
+    "{}{}{}{}",
+    timestamp,
+    request.method().to_string().to_uppercase(),
+    request.url(),
+    body
+  );
+
+  let expected: String = crate::sign(credentials.secret.expose(), &message).map_err(|_| error("Invalid API key"))?;
+
+  if sign != expected {
+    return Err(error("Invalid signature"));
+  }
+
+  Ok(())
+}
@@ -0,0 +1,690 @@
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::to_string;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::server::state::money;
+use crate::server::state::State;
+use crate::types::*;
+
+const DEFAULT_LIMIT: usize = 25;
+const MAX_LIMIT: usize = 100;
+const FEE: f64 = 0.015;
+
+///
+/// A parsed request to the mock server.
+///
+#[derive(Debug)]
+pub(crate) struct Request<'a> {
+  pub(crate) method: &'a str,
+  pub(crate) segments: Vec<&'a str>,
+  pub(crate) query: HashMap<String, String>,
+  pub(crate) body: Value,
+}
+
+///
+/// An error response in the Coinbase error envelope format.
+///
+#[derive(Debug)]
+pub(crate) struct ApiError {
+  pub(crate) status: u16,
+  pub(crate) id: &'static str,
+  pub(crate) message: String,
+}
+
+impl ApiError {
+  pub(crate) fn new(status: u16, id: &'static str, message: &str) -> Self {
+    Self {
+      status,
+      id,
+      message: message.to_owned(),
+    }
+  }
+
+  pub(crate) fn body(&self) -> String {
+    #[derive(Serialize)]
+    struct Errors {
+      errors: Vec<ErrorMessage>,
+    }
+
+    let errors: Vec<ErrorMessage> = vec![ErrorMessage {
+      id: self.id.to_owned(),
+      message: self.message.clone(),
+      url: None,
+    }];
+
+    to_string(&Errors { errors }).unwrap_or_default()
+  }
+}
+
+pub(crate) type Reply = Result<(u16, String), ApiError>;
+
+fn not_found() -> ApiError {
+  ApiError::new(404, "not_found", "Not found")
+}
+
+fn invalid(message: &str) -> ApiError {
+  ApiError::new(400, "validation_error", message)
+}
+
+fn reply<T: Serialize + Default>(status: u16, data: T, pagination: Option<Pagination>) -> Reply {
+  let response: Response<T> = Response {
+    data,
+    pagination,
+    errors: Vec::new(),
+    warnings: Vec::new(),
+  };
+
+  to_string(&response)
+    .map(|body| (status, body))
+    .map_err(|error| ApiError::new(500, "internal_server_error", &error.to_string()))
+}
+
+fn ok<T: Serialize + Default>(data: T) -> Reply {
+  reply(200, data, None)
+}
+
+fn created<T: Serialize + Default>(data: T) -> Reply {
+  reply(201, data, None)
+}
+
+fn found<T: Serialize + Default>(data: Option<T>) -> Reply {
+  data.map_or_else(|| Err(not_found()), ok)
+}
+
+fn number(value: &Value) -> Option<f64> {
+  match value {
+    Value::Number(number) => number.as_f64(),
+    Value::String(string) => string.parse().ok(),
+    _ => None,
+  }
+}
+
+fn string<'a>(body: &'a Value, key: &str) -> Option<&'a str> {
+  body.get(key).and_then(Value::as_str)
+}
+
+/// Returns one page of `items` (stored oldest first) according to the
+/// `limit`, `order`, `starting_after` and `ending_before` query parameters.
+fn paginate<T: Clone + Serialize + Default>(request: &Request, items: &[T], id: fn(&T) -> &str) -> Reply {
+  let limit: usize = request
+    .query
+    .get("limit")
+    .and_then(|limit| limit.parse().ok())
+    .unwrap_or(DEFAULT_LIMIT)
+    .clamp(1, MAX_LIMIT);
+
+  let order: Order = match request.query.get("order").map(String::as_str) {
+    Some("asc") => Order::ASC,
+    _ => Order::DESC,
+  };
+
+  let mut items: Vec<T> = items.to_vec();
+
+  if let Order::DESC = order {
+    items.reverse();
+  }
+
+  let position = |cursor: &str| items.iter().position(|item| id(item) == cursor);
+
+  let (start, end): (usize, usize) = if let Some(cursor) = request.query.get("starting_after") {
+    let start: usize = position(cursor).ok_or_else(|| invalid("Invalid starting_after"))? + 1;
+    (start, (start + limit).min(items.len()))
+  } else if let Some(cursor) = request.query.get("ending_before") {
+    let end: usize = position(cursor).ok_or_else(|| invalid("Invalid ending_before"))?;
+    (end.saturating_sub(limit), end)
+  } else {
+    (0, limit.min(items.len()))
+  };
+
+  let page: Vec<T> = items[start..end].to_vec();
+  let path: String = format!("/v2/{}", request.segments.join("/"));
+  let order_name: &str = if let Order::ASC = order { "asc" } else { "desc" };
+
+  let next_uri: Option<String> = if end < items.len() {
+    page.last().map(|last| {
+      format!(
+        "{}?limit={}&order={}&starting_after={}",
+        path,
+        limit,
+        order_name,
+        id(last)
+      )
+    })
+  } else {
+    None
+  };
+
+  let previous_uri: Option<String> = if start > 0 {
+    page.first().map(|first| {
+      format!(
+        "{}?limit={}&order={}&ending_before={}",
+        path,
+        limit,
+        order_name,
+        id(first)
+      )
+    })
+  } else {
+    None
+  };
+
+  let pagination: Pagination = Pagination {
+    limit,
+    order,
+    ending_before: request.query.get("ending_before").cloned(),
+    starting_after: request.query.get("starting_after").cloned(),
+    previous_uri,
+    next_uri,
+  };
+
+  reply(200, page, Some(pagination))
+}
+
+pub(crate) fn route(state: &mut State, request: &Request) -> Reply {
+  match (request.method, request.segments.as_slice()) {
+    ("GET", ["time"]) => time(),
+    ("GET", ["currencies"]) => ok(state.currencies.clone()),
+    ("GET", ["exchange-rates"]) => rates(state, request),
+    ("GET", ["prices", pair, kind]) => price(state, request, pair, kind),
+    ("GET", ["notifications"]) => paginate(request, &state.notifications, |item| &item.meta.id),
+    ("GET", ["notifications", id]) => found(state.notifications.iter().find(|item| item.meta.id == *id).cloned()),
+    ("GET", ["user"]) => ok(state.user.clone()),
+    ("GET", ["user", "auth"]) => ok(UserAuth {
+      method: "api_key".into(),
+      scopes: vec!["wallet:user:read".into(), "wallet:accounts:read".into()],
+      oauth_meta: None,
+    }),
+    ("PUT", ["user"]) => update_user(state, request),
+    ("GET", ["users", id]) => found(Some(state.user.clone()).filter(|user| user.meta.id == *id)),
+    ("GET", ["accounts"]) => paginate(request, &state.accounts, |item| &item.meta.id),
+    ("GET", ["accounts", id]) => found(state.account(id).cloned()),
+    ("PUT", ["accounts", id]) => update_account(state, request, id),
+    ("DELETE", ["accounts", id]) => delete_account(state, id),
+    ("POST", ["accounts", id, "primary"]) => primary_account(state, id),
+    ("GET", ["accounts", id, "addresses"]) => {
+      account(state, id)?;
+      paginate(request, list(&state.addresses, id), |item| &item.meta.id)
+    }
+    ("GET", ["accounts", id, "addresses", address]) => found(
+      list(&state.addresses, id)
+        .iter()
+        .find(|item| item.meta.id == *address)
+        .cloned(),
+    ),
+    ("POST", ["accounts", id, "addresses"]) => create_address(state, request, id),
+    ("GET", ["accounts", id, "transactions"]) => {
+      account(state, id)?;
+      paginate(request, list(&state.transactions, id), |item| &item.meta.id)
+    }
+    ("GET", ["accounts", id, "transactions", transaction]) => found(state.transaction(id, transaction).cloned()),
+    ("POST", ["accounts", id, "transactions"]) => create_transaction(state, request, id),
+    ("GET", ["accounts", id, "buys"]) => {
+      account(state, id)?;
+      paginate(request, list(&state.buys, id), |item| &item.meta.id)
+    }
+    ("GET", ["accounts", id, "buys", buy]) => {
+      found(list(&state.buys, id).iter().find(|item| item.meta.id == *buy).cloned())
+    }
+    ("POST", ["accounts", id, "buys"]) => create_trade(state, request, id, true),
+    ("POST", ["accounts", id, "buys", buy, "commit"]) => commit_trade(state, id, buy, true),
+    ("GET", ["accounts", id, "sells"]) => {
+      account(state, id)?;
+      paginate(request, list(&state.sells, id), |item| &item.meta.id)
+    }
+    ("GET", ["accounts", id, "sells", sell]) => found(
+      list(&state.sells, id)
+        .iter()
+        .find(|item| item.meta.id == *sell)
+        .cloned(),
+    ),
+    ("POST", ["accounts", id, "sells"]) => create_trade(state, request, id, false),
+    ("POST", ["accounts", id, "sells", sell, "commit"]) => commit_trade(state, id, sell, false),
+    ("GET", ["accounts", id, "deposits"]) => {
+      account(state, id)?;
+      paginate(request, list(&state.deposits, id), |item| &item.meta.id)
+    }
+    ("GET", ["accounts", id, "deposits", deposit]) => found(
+      list(&state.deposits, id)
+        .iter()
+        .find(|item| item.meta.id == *deposit)
+        .cloned(),
+    ),
+    ("GET", ["accounts", id, "withdrawals"]) => {
+      account(state, id)?;
+      paginate(request, list(&state.withdrawals, id), |item| &item.meta.id)
+    }
+    ("GET", ["accounts", id, "withdrawals", withdrawal]) => found(
+      list(&state.withdrawals, id)
+        .iter()
+        .find(|item| item.meta.id == *withdrawal)
+        .cloned(),
+    ),
+    ("GET", ["payment-methods"]) => paginate(request, &state.payment_methods, |item| &item.meta.id),
+    ("GET", ["payment-methods", id]) => found(state.payment_methods.iter().find(|item| item.meta.id == *id).cloned()),
+    _ => Err(not_found()),
+  }
+}
+
+fn list<'a, T>(items: &'a HashMap<String, Vec<T>>, account: &str) -> &'a [T] {
+  items.get(account).map(Vec::as_slice).unwrap_or_default()
+}
+
+fn account(state: &State, account: &str) -> Result<Account, ApiError> {
+  state.account(account).cloned().ok_or_else(not_found)
+}
+
+fn time() -> Reply {
+  let now = Utc::now();
+
+  ok(Time {
+    iso: now.into(),
+    epoch: now.timestamp() as f64,
+  })
+}
+
+fn rates(state: &State, request: &Request) -> Reply {
+  let currency: String = request.query.get("currency").cloned().unwrap_or_else(|| "USD".into());
+
+  let mut known: Vec<&str> = state
+    .prices
+    .keys()
+    .flat_map(|pair| pair.split('-'))
+    .chain(state.currencies.iter().map(|item| item.id.as_str()))
+    .collect();
+
+  known.sort_unstable();
+  known.dedup();
+
+  if !known.contains(&currency.as_str()) {
+    return Err(invalid("Invalid currency"));
+  }
+
+  let rates: SimpleMap = known
+    .into_iter()
+    .filter_map(|other| {
+      state
+        .price(&currency, other)
+        .map(|price| (other.to_owned(), price.to_string()))
+    })
+    .collect();
+
+  ok(Rates { currency, rates })
+}
+
+fn price(state: &State, request: &Request, pair: &str, kind: &str) -> Reply {
+  let mut parts = pair.splitn(2, '-');
+  let base: &str = parts.next().unwrap_or_default();
+  let quote: &str = parts.next().ok_or_else(|| invalid("Invalid currency pair"))?;
+
+  let spot: f64 = match request.query.get("date") {
+    Some(date) if kind == "spot" => state
+      .historic_price(base, quote, date)
+      .or_else(|| state.price(base, quote)),
+    _ => state.price(base, quote),
+  }
+  .ok_or_else(not_found)?;
+
+  let amount: f64 = match kind {
+    "spot" => spot,
+    "buy" => spot * (1.0 + state.spread),
+    "sell" => spot * (1.0 - state.spread),
+    _ => return Err(not_found()),
+  };
+
+  ok(Money {
+    base: Some(base.to_owned()),
+    ..money(amount, quote)
+  })
+}
+
+fn update_user(state: &mut State, request: &Request) -> Reply {
+  if let Some(name) = string(&request.body, "name") {
+    state.user.name = Some(name.to_owned());
+  }
+
+  if let Some(currency) = string(&request.body, "native_currency") {
+    state.user.native_currency = Some(currency.to_owned());
+  }
+
+  if let Some(time_zone) = string(&request.body, "time_zone") {
+    state.user.time_zone = Some(time_zone.to_owned());
+  }
+
+  ok(state.user.clone())
+}
+
+fn update_account(state: &mut State, request: &Request, id: &str) -> Reply {
+  let name: &str = string(&request.body, "name").ok_or_else(|| invalid("Missing name"))?;
+  let account: &mut Account = state.account_mut(id).ok_or_else(not_found)?;
+
+  account.name = name.to_owned();
+
+  ok(account.clone())
+}
+
+fn delete_account(state: &mut State, id: &str) -> Reply {
+  let account: Account = account(state, id)?;
+
+  if account.primary || account.balance.amount != 0.0 {
+    return Err(invalid("Account must be empty and not primary"));
+  }
+
+  state.accounts.retain(|item| item.meta.id != id);
+
+  Ok((204, String::new()))
+}
+
+fn primary_account(state: &mut State, id: &str) -> Reply {
+  account(state, id)?;
+
+  for account in state.accounts.iter_mut() {
+    account.primary = account.meta.id == id;
+  }
+
+  found(state.account(id).cloned())
+}
+
+fn create_address(state: &mut State, request: &Request, id: &str) -> Reply {
+  let account: Account = account(state, id)?;
+  let meta: ResourceMeta = state.meta(ResourceType::Address, &format!("accounts/{}/addresses", id));
+
+  let address: Address = Address {
+    name: string(&request.body, "name").map(ToOwned::to_owned),
+    address: format!("mock{}", meta.id.replace('-', "")),
+    network: account.balance.currency.to_lowercase(),
+    meta,
+    ..Default::default()
+  };
+
+  state.addresses.entry(id.to_owned()).or_default().push(address.clone());
+
+  created(address)
+}
+
+fn create_transaction(state: &mut State, request: &Request, id: &str) -> Reply {
+  let account: Account = account(state, id)?;
+  let body: &Value = &request.body;
+
+  let kind: &str = string(body, "type").ok_or_else(|| invalid("Missing type"))?;
+  let to: &str = string(body, "to").ok_or_else(|| invalid("Missing to"))?;
+  let currency: &str = string(body, "currency").ok_or_else(|| invalid("Missing currency"))?;
+
+  let amount: f64 = body
+    .get("amount")
+    .and_then(number)
+    .filter(|amount| *amount > 0.0)
+    .ok_or_else(|| invalid("Invalid amount"))?;
+
+  let amount: f64 = amount
+    * state
+      .price(currency, &account.balance.currency)
+      .ok_or_else(|| invalid("Invalid currency"))?;
+
+  let idem: Option<String> = string(body, "idem").map(ToOwned::to_owned);
+
+  if let Some(idem) = idem.as_ref() {
+    let existing = list(&state.transactions, id)
+      .iter()
+      .find(|item| item.idem.as_ref() == Some(idem));
+
+    if let Some(existing) = existing {
+      return ok(existing.clone());
+    }
+  }
+
+  let (kind, status, amount): (TransactionType, TransactionStatus, f64) = match kind {
+    "send" => (TransactionType::Send, TransactionStatus::Pending, -amount),
+    "transfer" => (TransactionType::Transfer, TransactionStatus::Completed, -amount),
+    "request" => (TransactionType::Request, TransactionStatus::Pending, amount),
+    _ => return Err(invalid("Invalid type")),
+  };
+
+  if amount < 0.0 && account.balance.amount + amount < 0.0 {
+    return Err(invalid("Insufficient funds"));
+  }
+
+  if let TransactionType::Transfer = kind {
+    let other: Account = state.account(to).cloned().ok_or_else(|| invalid("Invalid to"))?;
+
+    if other.balance.currency != account.balance.currency {
+      return Err(invalid("Invalid to"));
+    }
+
+    state.add_transaction(to, TransactionType::Transfer, TransactionStatus::Completed, -amount);
+  }
+
+  let meta: ResourceMeta = state.meta(ResourceType::Transaction, &format!("accounts/{}/transactions", id));
+
+  let recipient: ValueMap = match kind {
+    TransactionType::Transfer => vec![("resource", "account"), ("id", to)],
+    _ if to.contains('@') => vec![("resource", "email"), ("email", to)],
+    _ => vec![("resource", "address"), ("address", to)],
+  }
+  .into_iter()
+  .map(|(key, value)| (key.to_owned(), Value::String(value.to_owned())))
+  .collect();
+
+  let transaction: Transaction = Transaction {
+    meta,
+    kind,
+    amount: money(amount, &account.balance.currency),
+    native_amount: state.native(amount, &account.balance.currency),
+    description: string(body, "description").map(ToOwned::to_owned),
+    details: SimpleMap::new(),
+    to: Some(recipient),
+    idem,
+    ..Default::default()
+  };
+
+  created(state.insert_transaction(id, transaction, status))
+}
+
+fn create_trade(state: &mut State, request: &Request, id: &str, buy: bool) -> Reply {
+  let account: Account = account(state, id)?;
+  let body: &Value = &request.body;
+  let native: String = state.native_currency();
+
+  let currency: &str = string(body, "currency").ok_or_else(|| invalid("Missing currency"))?;
+  let commit: bool = body.get("commit").and_then(Value::as_bool).unwrap_or(true);
+  let idem: Option<String> = string(body, "idem").map(ToOwned::to_owned);
+
+  if let Some(trade) = idem.as_ref().and_then(|idem| state.trade_idems.get(idem)) {
+    return if buy {
+      found(
+        list(&state.buys, id)
+          .iter()
+          .find(|item| item.meta.id == *trade)
+          .cloned(),
+      )
+    } else {
+      found(
+        list(&state.sells, id)
+          .iter()
+          .find(|item| item.meta.id == *trade)
+          .cloned(),
+      )
+    };
+  }
+
+  let method: PaymentMethod = match string(body, "payment_method") {
+    Some(method) => state
+      .payment_methods
+      .iter()
+      .find(|item| item.meta.id == method)
+      .cloned()
+      .ok_or_else(|| invalid("Invalid payment method"))?,
+    None => state
+      .payment_methods
+      .first()
+      .cloned()
+      .ok_or_else(|| invalid("Missing payment method"))?,
+  };
+
+  let amount: f64 = body
+    .get("amount")
+    .and_then(number)
+    .filter(|amount| *amount > 0.0)
+    .ok_or_else(|| invalid("Invalid amount"))?;
+
+  let amount: f64 = amount
+    * state
+      .price(currency, &account.balance.currency)
+      .ok_or_else(|| invalid("Invalid currency"))?;
+
+  if !buy && account.balance.amount < amount {
+    return Err(invalid("Insufficient funds"));
+  }
+
+  let spot: f64 = state
+    .price(&account.balance.currency, &native)
+    .ok_or_else(|| invalid("Invalid currency"))?;
+
+  let unit: f64 = spot * if buy { 1.0 + state.spread } else { 1.0 - state.spread };
+  let subtotal: f64 = amount * unit;
+  let fee: f64 = subtotal * FEE;
+  let total: f64 = if buy { subtotal + fee } else { subtotal - fee };
+
+  let path: String = format!("accounts/{}/{}", id, if buy { "buys" } else { "sells" });
+  let meta: ResourceMeta = state.meta(if buy { ResourceType::Buy } else { ResourceType::Sell }, &path);
+
+  let payment_method: ResourceRef = ResourceRef {
+    id: method.meta.id.clone(),
+    resource: ResourceType::PaymentMethod,
+    resource_path: method.meta.resource_path.clone(),
+  };
+
+  if buy {
+    let buy: Buy = Buy {
+      meta,
+      status: TransferStatus::Created,
+      payment_method,
+      amount: money(amount, &account.balance.currency),
+      total: money(total, &native),
+      subtotal: money(subtotal, &native),
+      fee: money(fee, &native),
+      unit_price: Some(money(unit, &native)),
+      instant: true,
+      ..Default::default()
+    };
+
+    state.buys.entry(id.to_owned()).or_default().push(buy.clone());
+
+    if let Some(idem) = idem {
+      state.trade_idems.insert(idem, buy.meta.id.clone());
+    }
+
+    if commit {
+      return commit_trade(state, id, &buy.meta.id, true).map(|(_, body)| (201, body));
+    }
+
+    created(buy)
+  } else {
+    let sell: Sell = Sell {
+      meta,
+      status: TransferStatus::Created,
+      payment_method,
+      amount: money(amount, &account.balance.currency),
+      total: money(total, &native),
+      subtotal: money(subtotal, &native),
+      fee: money(fee, &native),
+      instant: true,
+      ..Default::default()
+    };
+
+    state.sells.entry(id.to_owned()).or_default().push(sell.clone());
+
+    if let Some(idem) = idem {
+      state.trade_idems.insert(idem, sell.meta.id.clone());
+    }
+
+    if commit {
+      return commit_trade(state, id, &sell.meta.id, false).map(|(_, body)| (201, body));
+    }
+
+    created(sell)
+  }
+}
+
+fn commit_trade(state: &mut State, id: &str, trade: &str, buy: bool) -> Reply {
+  let (status, amount, total): (TransferStatus, f64, Money) = if buy {
+    let item: &Buy = list(&state.buys, id)
+      .iter()
+      .find(|item| item.meta.id == trade)
+      .ok_or_else(not_found)?;
+
+    (item.status.clone(), item.amount.amount, item.total.clone())
+  } else {
+    let item: &Sell = list(&state.sells, id)
+      .iter()
+      .find(|item| item.meta.id == trade)
+      .ok_or_else(not_found)?;
+
+    (item.status.clone(), item.amount.amount, item.total.clone())
+  };
+
+  if !matches!(status, TransferStatus::Created) {
+    return Err(invalid("Already committed"));
+  }
+
+  let kind: TransactionType = if buy {
+    TransactionType::Buy
+  } else {
+    TransactionType::Sell
+  };
+  let amount: f64 = if buy { amount } else { -amount };
+
+  let transaction: Transaction = state
+    .add_transaction(id, kind, TransactionStatus::Completed, amount)
+    .ok_or_else(not_found)?;
+
+  let reference: ResourceRef = ResourceRef {
+    id: transaction.meta.id.clone(),
+    resource: ResourceType::Transaction,
+    resource_path: transaction.meta.resource_path.clone(),
+  };
+
+  let now: DateTime = Utc::now().into();
+  let mut native: Money = total;
+
+  native.amount = if buy { native.amount.abs() } else { -native.amount.abs() };
+
+  if let Some(item) = state
+    .transactions
+    .get_mut(id)
+    .and_then(|items| items.iter_mut().find(|item| item.meta.id == transaction.meta.id))
+  {
+    item.native_amount = native;
+  }
+
+  if buy {
+    let item: &mut Buy = state
+      .buys
+      .get_mut(id)
+      .and_then(|items| items.iter_mut().find(|item| item.meta.id == trade))
+      .ok_or_else(not_found)?;
+
+    item.status = TransferStatus::Completed;
+    item.committed = true;
+    item.transaction = reference;
+    item.payout_at = Some(now.clone());
+    item.meta.updated_at = Some(now);
+
+    ok(item.clone())
+  } else {
+    let item: &mut Sell = state
+      .sells
+      .get_mut(id)
+      .and_then(|items| items.iter_mut().find(|item| item.meta.id == trade))
+      .ok_or_else(not_found)?;
+
+    item.status = TransferStatus::Completed;
+    item.committed = true;
+    item.transaction = reference;
+    item.payout_at = Some(now.clone());
+    item.meta.updated_at = Some(now);
+
+    ok(item.clone())
+  }
+}
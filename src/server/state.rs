@@ -0,0 +1,320 @@
+use chrono::Utc;
+use std::collections::HashMap;
+
+use crate::types::*;
+
+///
+/// In-memory data served by the `MockServer`.
+///
+/// All fields are public so tests can seed or inspect the state directly;
+/// the helper methods keep balances, prices and resource metadata consistent.
+///
+#[derive(Clone, Debug)]
+pub struct State {
+  pub user: User,
+  pub currencies: Vec<Currency>,
+  pub prices: HashMap<String, f64>,
+  pub historic_prices: HashMap<String, f64>,
+  pub spread: f64,
+  pub accounts: Vec<Account>,
+  pub addresses: HashMap<String, Vec<Address>>,
+  pub transactions: HashMap<String, Vec<Transaction>>,
+  pub buys: HashMap<String, Vec<Buy>>,
+  pub sells: HashMap<String, Vec<Sell>>,
+  pub deposits: HashMap<String, Vec<Deposit>>,
+  pub withdrawals: HashMap<String, Vec<Withdrawal>>,
+  /// Buy and sell ids by the idempotency token they were placed with.
+  pub trade_idems: HashMap<String, String>,
+  pub payment_methods: Vec<PaymentMethod>,
+  pub notifications: Vec<Notification>,
+  next_id: u64,
+}
+
+impl Default for State {
+  fn default() -> Self {
+    let mut this: Self = Self::empty();
+
+    this.user = User {
+      meta: this.meta(ResourceType::User, "user"),
+      name: Some("Satoshi Nakamoto".into()),
+      username: Some("satoshi".into()),
+      avatar_url: "https://images.coinbase.com/avatar".into(),
+      time_zone: Some("UTC".into()),
+      native_currency: Some("USD".into()),
+      bitcoin_unit: Some("BTC".into()),
+      email: Some("satoshi@example.com".into()),
+      ..Default::default()
+    };
+
+    this.currencies = vec![
+      currency("EUR", "Euro", 0.01),
+      currency("GBP", "British Pound", 0.01),
+      currency("USD", "US Dollar", 0.01),
+    ];
+
+    this.set_price("BTC", "USD", 10_000.0);
+    this.set_price("BTC", "EUR", 9_000.0);
+    this.set_price("ETH", "USD", 200.0);
+    this.set_price("ETH", "EUR", 180.0);
+    this.set_price("LTC", "USD", 50.0);
+    this.set_price("LTC", "EUR", 45.0);
+    this.set_price("EUR", "USD", 1.1);
+
+    let usd: Account = this.add_account("USD Wallet", "USD", 1_000.0);
+
+    this.add_account("BTC Wallet", "BTC", 1.0);
+    this.add_account("ETH Wallet", "ETH", 10.0);
+
+    let method: PaymentMethod = PaymentMethod {
+      meta: this.meta(ResourceType::PaymentMethod, "payment-methods"),
+      kind: PaymentMethodType::FiatAccount,
+      name: usd.name,
+      currency: "USD".into(),
+      primary_buy: true,
+      primary_sell: true,
+      allow_buy: true,
+      allow_sell: true,
+      allow_deposit: true,
+      allow_withdraw: true,
+      instant_buy: true,
+      instant_sell: true,
+    };
+
+    this.payment_methods.push(method);
+    this
+  }
+}
+
+impl State {
+  /// Creates a state without any seed data.
+  pub fn empty() -> Self {
+    Self {
+      user: Default::default(),
+      currencies: Vec::new(),
+      prices: HashMap::new(),
+      historic_prices: HashMap::new(),
+      spread: 0.01,
+      accounts: Vec::new(),
+      addresses: HashMap::new(),
+      transactions: HashMap::new(),
+      buys: HashMap::new(),
+      sells: HashMap::new(),
+      deposits: HashMap::new(),
+      withdrawals: HashMap::new(),
+      trade_idems: HashMap::new(),
+      payment_methods: Vec::new(),
+      notifications: Vec::new(),
+      next_id: 0,
+    }
+  }
+
+  /// Returns a new unique resource id.
+  pub fn next_id(&mut self) -> String {
+    self.next_id += 1;
+
+    format!("00000000-0000-4000-8000-{:012x}", self.next_id)
+  }
+
+  /// Returns resource metadata for a new resource below `path`.
+  pub fn meta(&mut self, resource: ResourceType, path: &str) -> ResourceMeta {
+    let id: String = self.next_id();
+    let now: DateTime = Utc::now().into();
+
+    ResourceMeta {
+      resource_path: format!("/v2/{}/{}", path, id),
+      id,
+      resource,
+      created_at: Some(now.clone()),
+      updated_at: Some(now),
+    }
+  }
+
+  /// Sets the spot price of one unit of `base` in `quote`.
+  pub fn set_price(&mut self, base: &str, quote: &str, amount: f64) {
+    self.prices.insert(pair(base, quote), amount);
+  }
+
+  /// Sets the spot price of `base` in `quote` on `date` (`YYYY-MM-DD`).
+  pub fn set_historic_price(&mut self, base: &str, quote: &str, date: &str, amount: f64) {
+    self
+      .historic_prices
+      .insert(format!("{}@{}", pair(base, quote), date), amount);
+  }
+
+  /// Returns the spot price of `base` in `quote`, using the inverse rate if
+  /// only the opposite pair is known.
+  pub fn price(&self, base: &str, quote: &str) -> Option<f64> {
+    if base == quote {
+      return Some(1.0);
+    }
+
+    self
+      .prices
+      .get(&pair(base, quote))
+      .cloned()
+      .or_else(|| self.prices.get(&pair(quote, base)).map(|price| 1.0 / price))
+  }
+
+  /// Returns the spot price of `base` in `quote` on `date`.
+  pub fn historic_price(&self, base: &str, quote: &str, date: &str) -> Option<f64> {
+    self
+      .historic_prices
+      .get(&format!("{}@{}", pair(base, quote), date))
+      .cloned()
+  }
+
+  /// Returns the user's native currency.
+  pub fn native_currency(&self) -> String {
+    self.user.native_currency.clone().unwrap_or_else(|| "USD".into())
+  }
+
+  /// Converts `amount` of `currency` to the user's native currency.
+  pub fn native(&self, amount: f64, currency: &str) -> Money {
+    let native: String = self.native_currency();
+    let price: f64 = self.price(currency, &native).unwrap_or_default();
+
+    money(amount * price, &native)
+  }
+
+  /// Adds an account holding `balance` of `currency`.
+  pub fn add_account(&mut self, name: &str, currency: &str, balance: f64) -> Account {
+    let fiat: bool = self.currencies.iter().any(|item| item.id == currency);
+
+    let account: Account = Account {
+      meta: self.meta(ResourceType::Account, "accounts"),
+      kind: if fiat { AccountType::Fiat } else { AccountType::Wallet },
+      name: name.into(),
+      balance: money(balance, currency),
+      currency: AccountCurrency {
+        kind: if fiat { "fiat".into() } else { "crypto".into() },
+        name: currency.into(),
+        code: currency.into(),
+        exponent: if fiat { 2 } else { 8 },
+        ..Default::default()
+      },
+      primary: self.accounts.is_empty(),
+      allow_deposits: Some(true),
+      allow_withdrawals: Some(true),
+    };
+
+    self.accounts.push(account.clone());
+
+    account
+  }
+
+  /// Returns the account with id `account`.
+  pub fn account(&self, account: &str) -> Option<&Account> {
+    self.accounts.iter().find(|item| item.meta.id == account)
+  }
+
+  /// Returns the account with id `account` for modification.
+  pub fn account_mut(&mut self, account: &str) -> Option<&mut Account> {
+    self.accounts.iter_mut().find(|item| item.meta.id == account)
+  }
+
+  /// Adds a transaction to `account` and applies it to the balance if it is completed.
+  pub fn add_transaction(
+    &mut self,
+    account: &str,
+    kind: TransactionType,
+    status: TransactionStatus,
+    amount: f64,
+  ) -> Option<Transaction> {
+    let currency: String = self.account(account)?.balance.currency.clone();
+
+    let transaction: Transaction = Transaction {
+      meta: self.meta(ResourceType::Transaction, &format!("accounts/{}/transactions", account)),
+      kind,
+      amount: money(amount, &currency),
+      native_amount: self.native(amount, &currency),
+      details: SimpleMap::new(),
+      ..Default::default()
+    };
+
+    self.insert_transaction(account, transaction, status)
+  }
+
+  /// Inserts a prepared transaction into `account` with the given `status`.
+  pub fn insert_transaction(
+    &mut self,
+    account: &str,
+    mut transaction: Transaction,
+    status: TransactionStatus,
+  ) -> Option<Transaction> {
+    self.account(account)?;
+
+    transaction.status = TransactionStatus::Pending;
+
+    self
+      .transactions
+      .entry(account.to_owned())
+      .or_default()
+      .push(transaction.clone());
+
+    self.set_transaction_status(account, &transaction.meta.id, status)
+  }
+
+  /// Updates the status of a transaction, adjusting the account balance
+  /// when it becomes (or stops being) completed.
+  pub fn set_transaction_status(
+    &mut self,
+    account: &str,
+    transaction: &str,
+    status: TransactionStatus,
+  ) -> Option<Transaction> {
+    let item: &mut Transaction = self
+      .transactions
+      .get_mut(account)?
+      .iter_mut()
+      .find(|item| item.meta.id == transaction)?;
+
+    let was: bool = matches!(item.status, TransactionStatus::Completed);
+    let now: bool = matches!(status, TransactionStatus::Completed);
+    let amount: f64 = item.amount.amount;
+
+    item.status = status;
+    item.meta.updated_at = Some(Utc::now().into());
+
+    let item: Transaction = item.clone();
+
+    if let Some(account) = self.account_mut(account) {
+      if now && !was {
+        account.balance.amount += amount;
+      } else if was && !now {
+        account.balance.amount -= amount;
+      }
+    }
+
+    Some(item)
+  }
+
+  /// Returns the transaction with id `transaction` of `account`.
+  pub fn transaction(&self, account: &str, transaction: &str) -> Option<&Transaction> {
+    self
+      .transactions
+      .get(account)?
+      .iter()
+      .find(|item| item.meta.id == transaction)
+  }
+}
+
+/// Returns `amount` of `currency` as `Money`.
+pub fn money(amount: f64, currency: &str) -> Money {
+  Money {
+    amount,
+    currency: currency.into(),
+    ..Default::default()
+  }
+}
+
+fn currency(id: &str, name: &str, min_size: f64) -> Currency {
+  Currency {
+    id: id.into(),
+    name: name.into(),
+    min_size,
+  }
+}
+
+fn pair(base: &str, quote: &str) -> String {
+  format!("{}-{}", base, quote)
+}
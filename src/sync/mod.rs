@@ -0,0 +1,230 @@
+mod store;
+
+pub use self::store::AccountSnapshot;
+pub use self::store::Cursors;
+pub use self::store::JsonStore;
+pub use self::store::MemoryStore;
+pub use self::store::Snapshot;
+pub use self::store::Store;
+
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::types::Account;
+use crate::types::Buy;
+use crate::types::Sell;
+use crate::types::Transaction;
+use crate::types::TransactionStatus;
+use crate::types::TransferStatus;
+
+/// Error ids returned for a `starting_after` cursor that no longer exists.
+const CURSOR_ERRORS: &[&str] = &["validation_error", "invalid_request", "not_found"];
+
+///
+/// What changed during a sync.
+///
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct SyncReport {
+  /// Number of accounts synced.
+  pub accounts: usize,
+  /// Records fetched for the first time.
+  pub added: usize,
+  /// Stored records that changed.
+  pub updated: usize,
+  /// Stored records that were no longer found (including closed accounts).
+  pub removed: usize,
+  /// Non-final records that were fetched again.
+  pub rechecked: usize,
+}
+
+impl Client {
+  /// Syncs all accounts with their transactions, buys and sells into `store`.
+  ///
+  /// Only records after the stored cursors are listed; stored records with a
+  /// non-final status (e.g. `Pending` or `WaitingForClearing` transactions,
+  /// uncommitted buys) are fetched again. The store is saved after each
+  /// account.
+  ///
+  /// ```no_run
+  /// # use coinbase::prelude::*;
+  /// # use coinbase::sync::JsonStore;
+  /// # fn run(client: &Client) -> Result<(), Error> {
+  /// let report = client.sync(JsonStore::new("coinbase.json"))?;
+  ///
+  /// println!("{} new, {} updated", report.added, report.updated);
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn sync<S: Store>(&self, mut store: S) -> Result<SyncReport, Error> {
+    let mut snapshot: Snapshot = store.load()?;
+    let mut report: SyncReport = SyncReport::default();
+    let accounts: Vec<Account> = self.all_accounts()?;
+
+    snapshot.accounts.retain(|id, stored| {
+      let open: bool = accounts.iter().any(|account| account.meta.id == *id);
+
+      if !open {
+        report.removed += 1 + stored.transactions.len() + stored.buys.len() + stored.sells.len();
+      }
+
+      open
+    });
+
+    for account in accounts {
+      let id: String = account.meta.id.clone();
+      let stored: &mut AccountSnapshot = snapshot.accounts.entry(id.clone()).or_default();
+
+      stored.account = account;
+
+      self.sync_records(
+        &id,
+        &mut stored.transactions,
+        &mut stored.cursors.transactions,
+        &mut report,
+      )?;
+      self.sync_records(&id, &mut stored.buys, &mut stored.cursors.buys, &mut report)?;
+      self.sync_records(&id, &mut stored.sells, &mut stored.cursors.sells, &mut report)?;
+
+      stored.synced_at = Some(Utc::now());
+      report.accounts += 1;
+
+      store.save(&snapshot)?;
+    }
+
+    Ok(report)
+  }
+
+  fn sync_records<T: Record>(
+    &self,
+    account: &str,
+    records: &mut Vec<T>,
+    cursor: &mut Option<String>,
+    report: &mut SyncReport,
+  ) -> Result<(), Error> {
+    let mut index: usize = 0;
+
+    while index < records.len() {
+      if records[index].is_final() {
+        index += 1;
+        continue;
+      }
+
+      report.rechecked += 1;
+
+      match T::fetch(self, account, records[index].id()) {
+        Ok(record) => {
+          report.updated += merge(&mut records[index], record) as usize;
+          index += 1;
+        }
+        Err(Error::API(errors)) if errors.iter().any(|error| error.id == "not_found") => {
+          records.remove(index);
+          report.removed += 1;
+        }
+        Err(error) => return Err(error),
+      }
+    }
+
+    let path: String = format!("accounts/{}/{}", account, T::RESOURCE);
+
+    // A cursor is rejected when its record no longer exists; list everything
+    // again and merge instead. Other errors (e.g. authentication) are returned.
+    let fetched: Vec<T> = match self.get_after(&path, cursor.as_deref()) {
+      Err(Error::API(errors)) if cursor.is_some() && errors.iter().any(|error| CURSOR_ERRORS.contains(&&*error.id)) => {
+        self.get_all(&path)?
+      }
+      fetched => fetched?,
+    };
+
+    for record in fetched {
+      match records.iter_mut().find(|stored| stored.id() == record.id()) {
+        Some(stored) => report.updated += merge(stored, record) as usize,
+        None => {
+          records.push(record);
+          report.added += 1;
+        }
+      }
+    }
+
+    *cursor = records.last().map(|record| record.id().to_owned());
+
+    Ok(())
+  }
+}
+
+/// A record synced per account.
+trait Record: Clone + Default + DeserializeOwned + Serialize {
+  /// The collection name in `accounts/{id}/<resource>`.
+  const RESOURCE: &'static str;
+
+  fn id(&self) -> &str;
+
+  /// Returns `true` if the record can no longer change.
+  fn is_final(&self) -> bool;
+
+  fn fetch(client: &Client, account: &str, id: &str) -> Result<Self, Error>;
+}
+
+impl Record for Transaction {
+  const RESOURCE: &'static str = "transactions";
+
+  fn id(&self) -> &str {
+    &self.meta.id
+  }
+
+  fn is_final(&self) -> bool {
+    !matches!(
+      self.status,
+      TransactionStatus::Pending
+        | TransactionStatus::WaitingForSignature
+        | TransactionStatus::WaitingForClearing
+        | TransactionStatus::Unknown
+    )
+  }
+
+  fn fetch(client: &Client, account: &str, id: &str) -> Result<Self, Error> {
+    client.get_transaction(account, id)?.into_result()
+  }
+}
+
+impl Record for Buy {
+  const RESOURCE: &'static str = "buys";
+
+  fn id(&self) -> &str {
+    &self.meta.id
+  }
+
+  fn is_final(&self) -> bool {
+    matches!(self.status, TransferStatus::Completed | TransferStatus::Canceled)
+  }
+
+  fn fetch(client: &Client, account: &str, id: &str) -> Result<Self, Error> {
+    client.get_buy(account, id)?.into_result()
+  }
+}
+
+impl Record for Sell {
+  const RESOURCE: &'static str = "sells";
+
+  fn id(&self) -> &str {
+    &self.meta.id
+  }
+
+  fn is_final(&self) -> bool {
+    matches!(self.status, TransferStatus::Completed | TransferStatus::Canceled)
+  }
+
+  fn fetch(client: &Client, account: &str, id: &str) -> Result<Self, Error> {
+    client.get_sell(account, id)?.into_result()
+  }
+}
+
+/// Replaces `stored` with `record`, returning `true` if it changed.
+fn merge<T: Record>(stored: &mut T, record: T) -> bool {
+  let changed: bool = serde_json::to_value(&*stored).ok() != serde_json::to_value(&record).ok();
+
+  *stored = record;
+  changed
+}
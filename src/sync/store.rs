@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::fs::rename;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::error::Error;
+use crate::types::Account;
+use crate::types::Buy;
+use crate::types::Sell;
+use crate::types::Transaction;
+use crate::types::UtcDate;
+
+///
+/// The ids of the newest records fetched for an account, used as
+/// `starting_after` cursors on the next sync.
+///
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Cursors {
+  pub transactions: Option<String>,
+  pub buys: Option<String>,
+  pub sells: Option<String>,
+}
+
+///
+/// An account and its records as stored locally, oldest first.
+///
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AccountSnapshot {
+  pub account: Account,
+  pub transactions: Vec<Transaction>,
+  pub buys: Vec<Buy>,
+  pub sells: Vec<Sell>,
+  pub cursors: Cursors,
+  pub synced_at: Option<UtcDate>,
+}
+
+///
+/// Everything a `Store` persists, keyed by account id.
+///
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Snapshot {
+  pub accounts: BTreeMap<String, AccountSnapshot>,
+}
+
+///
+/// Persistent storage for synced records.
+///
+/// `save` is called after each account is synced, so an interrupted sync
+/// resumes from the last saved cursors.
+///
+pub trait Store {
+  /// Loads the stored snapshot, or an empty one if nothing has been saved.
+  fn load(&mut self) -> Result<Snapshot, Error>;
+
+  /// Replaces the stored snapshot.
+  fn save(&mut self, snapshot: &Snapshot) -> Result<(), Error>;
+}
+
+impl<S: Store + ?Sized> Store for &mut S {
+  fn load(&mut self) -> Result<Snapshot, Error> {
+    (**self).load()
+  }
+
+  fn save(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+    (**self).save(snapshot)
+  }
+}
+
+///
+/// A `Store` that keeps the snapshot in memory.
+///
+#[derive(Clone, Debug, Default)]
+pub struct MemoryStore {
+  snapshot: Snapshot,
+}
+
+impl MemoryStore {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Returns the stored snapshot.
+  pub fn snapshot(&self) -> &Snapshot {
+    &self.snapshot
+  }
+}
+
+impl Store for MemoryStore {
+  fn load(&mut self) -> Result<Snapshot, Error> {
+    Ok(self.snapshot.clone())
+  }
+
+  fn save(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+    self.snapshot = snapshot.clone();
+    Ok(())
+  }
+}
+
+///
+/// A `Store` that keeps the snapshot in a JSON file.
+///
+/// The file is written to a temporary file next to it and then renamed, so
+/// an interrupted save never leaves a truncated snapshot behind.
+///
+#[derive(Clone, Debug)]
+pub struct JsonStore {
+  path: PathBuf,
+}
+
+impl JsonStore {
+  pub fn new<P: AsRef<Path>>(path: P) -> Self {
+    Self {
+      path: path.as_ref().to_owned(),
+    }
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+}
+
+impl Store for JsonStore {
+  fn load(&mut self) -> Result<Snapshot, Error> {
+    match File::open(&self.path) {
+      Ok(file) => Ok(serde_json::from_reader(BufReader::new(file))?),
+      Err(error) if error.kind() == ErrorKind::NotFound => Ok(Snapshot::default()),
+      Err(error) => Err(error.into()),
+    }
+  }
+
+  fn save(&mut self, snapshot: &Snapshot) -> Result<(), Error> {
+    let temporary: PathBuf = self.path.with_extension("tmp");
+    let mut writer: BufWriter<File> = BufWriter::new(File::create(&temporary)?);
+
+    serde_json::to_writer(&mut writer, snapshot)?;
+    writer.flush()?;
+    drop(writer);
+
+    rename(&temporary, &self.path)?;
+
+    Ok(())
+  }
+}
@@ -0,0 +1,127 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use crate::types::Buy;
+use crate::types::Money;
+use crate::types::Sell;
+use crate::types::Transaction;
+use crate::types::TransactionStatus;
+use crate::types::TransactionType;
+use crate::types::TransferStatus;
+use crate::types::UtcDate;
+
+///
+/// Whether a `TaxEvent` adds to or removes from holdings.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+  Acquire,
+  Dispose,
+}
+
+///
+/// An acquisition or disposal of a currency, valued in the native currency.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TaxEvent {
+  /// Id of the buy, sell or transaction the event was created from.
+  pub id: String,
+  pub time: UtcDate,
+  pub kind: EventKind,
+  pub currency: String,
+  /// Units acquired or disposed (always positive).
+  pub amount: Decimal,
+  /// Cost of an acquisition (fees included) or proceeds of a disposal (fees
+  /// deducted), in the native currency. `None` when it must be looked up.
+  pub native_value: Option<Decimal>,
+  /// Fee paid, in the native currency.
+  pub fee: Decimal,
+}
+
+impl TaxEvent {
+  /// Creates an acquisition from a completed buy. The value is taken from
+  /// `total` when it is in `native`.
+  pub fn from_buy(buy: &Buy, native: &str) -> Option<Self> {
+    if !matches!(buy.status, TransferStatus::Completed) {
+      return None;
+    }
+
+    Some(Self {
+      id: buy.meta.id.clone(),
+      time: buy.meta.created(),
+      kind: EventKind::Acquire,
+      currency: buy.amount.currency.clone(),
+      amount: crate::decimal(buy.amount.amount).abs(),
+      native_value: native_value(&buy.total, native),
+      fee: native_value(&buy.fee, native).unwrap_or_default(),
+    })
+  }
+
+  /// Creates a disposal from a completed sell. The value is taken from
+  /// `total` when it is in `native`.
+  pub fn from_sell(sell: &Sell, native: &str) -> Option<Self> {
+    if !matches!(sell.status, TransferStatus::Completed) {
+      return None;
+    }
+
+    Some(Self {
+      id: sell.meta.id.clone(),
+      time: sell.meta.created(),
+      kind: EventKind::Dispose,
+      currency: sell.amount.currency.clone(),
+      amount: crate::decimal(sell.amount.amount).abs(),
+      native_value: native_value(&sell.total, native),
+      fee: native_value(&sell.fee, native).unwrap_or_default(),
+    })
+  }
+
+  /// Creates an event from a completed transaction that changes holdings.
+  ///
+  /// Buys, sells and sends are taxable; transfers between the user's own
+  /// accounts, fiat deposits and withdrawals, and requests are not. The value
+  /// is taken from `native_amount` when it is in `native`.
+  pub fn from_transaction(transaction: &Transaction, native: &str) -> Option<Self> {
+    if !matches!(transaction.status, TransactionStatus::Completed) {
+      return None;
+    }
+
+    let amount: Decimal = crate::decimal(transaction.amount.amount);
+
+    let kind: EventKind = match transaction.kind {
+      TransactionType::Buy => EventKind::Acquire,
+      TransactionType::Sell => EventKind::Dispose,
+      TransactionType::Send if amount.is_sign_positive() => EventKind::Acquire,
+      TransactionType::Send => EventKind::Dispose,
+      _ => return None,
+    };
+
+    if amount.is_zero() || transaction.amount.currency.eq_ignore_ascii_case(native) {
+      return None;
+    }
+
+    let native_value: Option<Decimal> =
+      native_value(&transaction.native_amount, native).filter(|value| !value.is_zero());
+
+    Some(Self {
+      id: transaction.meta.id.clone(),
+      time: transaction.meta.created(),
+      kind,
+      currency: transaction.amount.currency.clone(),
+      amount: amount.abs(),
+      native_value,
+      fee: Decimal::ZERO,
+    })
+  }
+
+  /// Returns the day of the event, used to look up historic prices.
+  pub fn date(&self) -> NaiveDate {
+    self.time.date_naive()
+  }
+}
+
+fn native_value(money: &Money, native: &str) -> Option<Decimal> {
+  Some(money)
+    .filter(|money| money.currency.eq_ignore_ascii_case(native))
+    .map(|money| crate::decimal(money.amount).abs())
+}
@@ -0,0 +1,241 @@
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+use crate::tax::EventKind;
+use crate::tax::TaxEvent;
+use crate::types::UtcDate;
+
+///
+/// How disposals are matched against open lots.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Method {
+  /// First in, first out.
+  #[default]
+  FIFO,
+  /// Last in, first out.
+  LIFO,
+  /// Highest unit cost first.
+  HIFO,
+  /// All holdings of a currency form one lot at their average cost.
+  Average,
+}
+
+///
+/// Units of a currency acquired together, with their remaining cost basis.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Lot {
+  /// Id of the acquiring event (of the first one for average-cost lots).
+  pub id: String,
+  pub currency: String,
+  pub acquired: UtcDate,
+  pub amount: Decimal,
+  pub cost: Decimal,
+}
+
+impl Lot {
+  /// Returns the cost basis of one unit.
+  pub fn unit_cost(&self) -> Decimal {
+    if self.amount.is_zero() {
+      Decimal::ZERO
+    } else {
+      self.cost / self.amount
+    }
+  }
+}
+
+///
+/// A realized gain (or loss) from disposing of (part of) a lot.
+///
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Gain {
+  /// Id of the disposing event.
+  pub id: String,
+  /// Id of the matched lot, `None` for units disposed without a known
+  /// acquisition (which have a zero cost basis).
+  pub lot: Option<String>,
+  pub currency: String,
+  pub acquired: Option<UtcDate>,
+  pub disposed: UtcDate,
+  pub amount: Decimal,
+  pub proceeds: Decimal,
+  pub cost: Decimal,
+  pub gain: Decimal,
+}
+
+///
+/// Tracks open lots and realized gains per currency.
+///
+/// Events must be applied in chronological order and carry a native value.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Ledger {
+  method: Method,
+  lots: BTreeMap<String, Vec<Lot>>,
+  gains: Vec<Gain>,
+}
+
+impl Ledger {
+  pub fn new(method: Method) -> Self {
+    Self {
+      method,
+      ..Default::default()
+    }
+  }
+
+  pub fn method(&self) -> Method {
+    self.method
+  }
+
+  /// Applies an acquisition or disposal; events without a native value are
+  /// valued at zero.
+  pub fn apply(&mut self, event: &TaxEvent) {
+    match event.kind {
+      EventKind::Acquire => self.acquire(event),
+      EventKind::Dispose => self.dispose(event),
+    }
+  }
+
+  /// Returns the realized gains, in the order they occurred.
+  pub fn gains(&self) -> &[Gain] {
+    &self.gains
+  }
+
+  /// Returns the open lots of `currency`.
+  pub fn lots(&self, currency: &str) -> &[Lot] {
+    self.lots.get(currency).map(Vec::as_slice).unwrap_or_default()
+  }
+
+  /// Returns the currencies with open lots.
+  pub fn currencies(&self) -> impl Iterator<Item = &str> {
+    self
+      .lots
+      .iter()
+      .filter(|(_, lots)| !lots.is_empty())
+      .map(|(currency, _)| currency.as_str())
+  }
+
+  /// Returns the units of `currency` held in open lots.
+  pub fn holdings(&self, currency: &str) -> Decimal {
+    self.lots(currency).iter().map(|lot| lot.amount).sum()
+  }
+
+  /// Returns the remaining cost basis of `currency`.
+  pub fn cost_basis(&self, currency: &str) -> Decimal {
+    self.lots(currency).iter().map(|lot| lot.cost).sum()
+  }
+
+  /// Returns the total realized gain of `currency`.
+  pub fn realized(&self, currency: &str) -> Decimal {
+    self
+      .gains
+      .iter()
+      .filter(|gain| gain.currency == currency)
+      .map(|gain| gain.gain)
+      .sum()
+  }
+
+  /// Returns the unrealized gain of `currency` at a unit `price`.
+  pub fn unrealized(&self, currency: &str, price: Decimal) -> Decimal {
+    self.holdings(currency) * price - self.cost_basis(currency)
+  }
+
+  fn acquire(&mut self, event: &TaxEvent) {
+    let lot: Lot = Lot {
+      id: event.id.clone(),
+      currency: event.currency.clone(),
+      acquired: event.time,
+      amount: event.amount,
+      cost: event.native_value.unwrap_or_default(),
+    };
+
+    let lots: &mut Vec<Lot> = self.lots.entry(event.currency.clone()).or_default();
+
+    match (self.method, lots.first_mut()) {
+      (Method::Average, Some(pool)) => {
+        pool.amount += lot.amount;
+        pool.cost += lot.cost;
+      }
+      _ => lots.push(lot),
+    }
+  }
+
+  fn dispose(&mut self, event: &TaxEvent) {
+    let method: Method = self.method;
+    let lots: &mut Vec<Lot> = self.lots.entry(event.currency.clone()).or_default();
+    let proceeds: Decimal = event.native_value.unwrap_or_default();
+    let mut remaining: Decimal = event.amount;
+
+    while remaining > Decimal::ZERO {
+      let index: usize = match next(lots, method) {
+        Some(index) => index,
+        None => break,
+      };
+
+      let lot: &mut Lot = &mut lots[index];
+      let amount: Decimal = remaining.min(lot.amount);
+      let cost: Decimal = if amount == lot.amount {
+        lot.cost
+      } else {
+        lot.cost * amount / lot.amount
+      };
+
+      let share: Decimal = proceeds * amount / event.amount;
+
+      self.gains.push(Gain {
+        id: event.id.clone(),
+        lot: Some(lot.id.clone()),
+        currency: event.currency.clone(),
+        acquired: Some(lot.acquired),
+        disposed: event.time,
+        amount,
+        proceeds: share,
+        cost,
+        gain: share - cost,
+      });
+
+      lot.amount -= amount;
+      lot.cost -= cost;
+      remaining -= amount;
+
+      if lot.amount.is_zero() {
+        lots.remove(index);
+      }
+    }
+
+    if remaining > Decimal::ZERO {
+      let share: Decimal = proceeds * remaining / event.amount;
+
+      self.gains.push(Gain {
+        id: event.id.clone(),
+        lot: None,
+        currency: event.currency.clone(),
+        acquired: None,
+        disposed: event.time,
+        amount: remaining,
+        proceeds: share,
+        cost: Decimal::ZERO,
+        gain: share,
+      });
+    }
+  }
+}
+
+fn next(lots: &[Lot], method: Method) -> Option<usize> {
+  if lots.is_empty() {
+    return None;
+  }
+
+  match method {
+    Method::FIFO | Method::Average => Some(0),
+    Method::LIFO => Some(lots.len() - 1),
+    Method::HIFO => lots
+      .iter()
+      .enumerate()
+      .rev()
+      .max_by(|(_, a), (_, b)| a.unit_cost().cmp(&b.unit_cost()))
+      .map(|(index, _)| index),
+  }
+}
@@ -0,0 +1,98 @@
+mod event;
+mod ledger;
+
+pub use self::event::EventKind;
+pub use self::event::TaxEvent;
+pub use self::ledger::Gain;
+pub use self::ledger::Ledger;
+pub use self::ledger::Lot;
+pub use self::ledger::Method;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::types::Account;
+use crate::types::User;
+
+impl Client {
+  /// Collects the taxable events of all accounts, valued in the native
+  /// currency of the current user and sorted by time.
+  ///
+  /// Buys and sells are taken from their own resources (which include fees);
+  /// other transactions are valued from `native_amount`, or from the historic
+  /// spot price on the day they were created when that is missing.
+  pub fn tax_events(&self) -> Result<Vec<TaxEvent>, Error> {
+    let user: User = self.current_user()?.into_result()?;
+    let native: String = user.native_currency.unwrap_or_else(|| "USD".to_owned());
+    let mut events: Vec<TaxEvent> = Vec::new();
+
+    for account in self.all_accounts()? {
+      if account.balance.currency.eq_ignore_ascii_case(&native) {
+        continue;
+      }
+
+      events.extend(self.account_events(&account, &native)?);
+    }
+
+    let mut prices: HashMap<(String, NaiveDate), Decimal> = HashMap::new();
+
+    for event in events.iter_mut().filter(|event| event.native_value.is_none()) {
+      let key: (String, NaiveDate) = (event.currency.clone(), event.date());
+
+      let price: Decimal = match prices.get(&key) {
+        Some(price) => *price,
+        None => {
+          let price: Decimal = crate::decimal(self.historic_spot_price(&key.0, &native, key.1)?.into_result()?.amount);
+
+          prices.insert(key, price);
+          price
+        }
+      };
+
+      event.native_value = Some(price * event.amount);
+    }
+
+    events.sort_by(|a, b| a.time.cmp(&b.time).then_with(|| a.kind.cmp(&b.kind)));
+
+    Ok(events)
+  }
+
+  /// Builds a `Ledger` of all accounts using the given lot matching `method`.
+  pub fn tax_ledger(&self, method: Method) -> Result<Ledger, Error> {
+    let mut ledger: Ledger = Ledger::new(method);
+
+    for event in self.tax_events()? {
+      ledger.apply(&event);
+    }
+
+    Ok(ledger)
+  }
+
+  fn account_events(&self, account: &Account, native: &str) -> Result<Vec<TaxEvent>, Error> {
+    let id: &str = &account.meta.id;
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut events: Vec<TaxEvent> = Vec::new();
+
+    for buy in self.all_buys(id)? {
+      seen.insert(buy.transaction.id.clone());
+      events.extend(TaxEvent::from_buy(&buy, native));
+    }
+
+    for sell in self.all_sells(id)? {
+      seen.insert(sell.transaction.id.clone());
+      events.extend(TaxEvent::from_sell(&sell, native));
+    }
+
+    for transaction in self.all_transactions(id)? {
+      if !seen.contains(&transaction.meta.id) {
+        events.extend(TaxEvent::from_transaction(&transaction, native));
+      }
+    }
+
+    Ok(events)
+  }
+}
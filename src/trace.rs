@@ -0,0 +1,92 @@
+use reqwest::Method;
+use reqwest::Url;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::transport::HttpResponse;
+
+///
+/// Instrumentation of one API call, emitted as a `tracing` span when the
+/// `tracing` feature is enabled.
+///
+/// Only the endpoint template, method, status, latency, pagination cursor
+/// and error id are recorded - never headers or bodies.
+///
+#[cfg(feature = "tracing")]
+pub(crate) struct Trace {
+  span: tracing::Span,
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Trace;
+
+#[cfg(feature = "tracing")]
+impl Trace {
+  pub(crate) fn new(method: &Method, base: &Url, url: &Url) -> Self {
+    let span: tracing::Span = tracing::info_span!(
+      "coinbase.request",
+      endpoint = %crate::endpoint::template_url(base, url),
+      method = %method,
+      cursor = crate::endpoint::cursor(url).as_deref(),
+      status = tracing::field::Empty,
+      latency_ms = tracing::field::Empty,
+      attempts = tracing::field::Empty,
+      error_id = tracing::field::Empty,
+    );
+
+    Self { span }
+  }
+
+  pub(crate) fn response(&self, response: &HttpResponse, latency: Duration, attempt: u32) {
+    let status: u16 = response.status.as_u16();
+    let latency: u64 = latency.as_millis() as u64;
+    let error: Option<String> = crate::error_id(&response.body);
+
+    self.span.record("status", status);
+    self.span.record("latency_ms", latency);
+    self.span.record("attempts", attempt);
+
+    if let Some(error) = error.as_deref() {
+      self.span.record("error_id", error);
+    }
+
+    tracing::debug!(parent: &self.span, status, latency_ms = latency, attempt, error_id = error.as_deref(), "response");
+  }
+
+  pub(crate) fn error(&self, error: &Error, latency: Duration, attempt: u32) {
+    let latency: u64 = latency.as_millis() as u64;
+
+    self.span.record("latency_ms", latency);
+    self.span.record("attempts", attempt);
+
+    tracing::warn!(parent: &self.span, error = kind(error), latency_ms = latency, attempt, "request failed");
+  }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl Trace {
+  pub(crate) fn new(_method: &Method, _base: &Url, _url: &Url) -> Self {
+    Trace
+  }
+
+  pub(crate) fn response(&self, _response: &HttpResponse, _latency: Duration, _attempt: u32) {}
+
+  pub(crate) fn error(&self, _error: &Error, _latency: Duration, _attempt: u32) {}
+}
+
+#[cfg(feature = "tracing")]
+fn kind(error: &Error) -> &'static str {
+  match error {
+    Error::HTTP(_) => "http",
+    Error::JSON { .. } => "json",
+    Error::InvalidAuth => "invalid_auth",
+    Error::URL(_) => "url",
+    Error::IO(_) => "io",
+    Error::TOML(_) => "toml",
+    Error::Config(_) => "config",
+    Error::Unmatched { .. } => "unmatched",
+    Error::API(_) => "api",
+    Error::CSV(_) => "csv",
+    Error::Schedule(_) => "schedule",
+  }
+}
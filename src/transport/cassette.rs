@@ -0,0 +1,200 @@
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
+use reqwest::StatusCode;
+use serde_json::from_str;
+use serde_json::to_string_pretty;
+use std::collections::BTreeMap;
+use std::env::var;
+use std::fs::read_to_string;
+use std::fs::write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use crate::error::Error;
+use crate::transport::HttpRequest;
+use crate::transport::HttpResponse;
+use crate::transport::Transport;
+
+/// Environment variable that switches `Cassette::from_env` to record mode.
+pub const ENV_CASSETTE: &str = "COINBASE_CASSETTE";
+
+const SCRUBBED: &[&str] = &[
+  "cb-access-key",
+  "cb-access-sign",
+  "cb-access-timestamp",
+  "authorization",
+];
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct RecordedRequest {
+  method: String,
+  url: String,
+  headers: BTreeMap<String, String>,
+  body: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct RecordedResponse {
+  status: u16,
+  headers: BTreeMap<String, String>,
+  body: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct Interaction {
+  request: RecordedRequest,
+  response: RecordedResponse,
+  #[serde(skip)]
+  played: bool,
+}
+
+impl Interaction {
+  fn matches(&self, request: &HttpRequest) -> bool {
+    !self.played
+      && self.request.method == request.method.as_str()
+      && self.request.url == target(request)
+      && self.request.body == request.body
+  }
+}
+
+#[derive(Debug)]
+enum Mode {
+  Record(Arc<dyn Transport>),
+  Replay,
+}
+
+///
+/// A `Transport` that records request/response pairs to a file, or replays
+/// them without network access.
+///
+/// Recorded requests are stored without their `CB-ACCESS-*` headers and are
+/// matched by method, path, query and body; each recorded interaction is
+/// replayed once, in order. Unmatched requests fail with `Error::Unmatched`.
+///
+#[derive(Debug)]
+pub struct Cassette {
+  mode: Mode,
+  path: PathBuf,
+  interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Cassette {
+  /// Creates a cassette that forwards requests to `inner` and writes every
+  /// interaction to `path`, replacing any previous recording.
+  pub fn record<P: AsRef<Path>, T: Transport + 'static>(path: P, inner: T) -> Self {
+    Self {
+      mode: Mode::Record(Arc::new(inner)),
+      path: path.as_ref().to_owned(),
+      interactions: Mutex::new(Vec::new()),
+    }
+  }
+
+  /// Loads the cassette at `path` for replay.
+  pub fn replay<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+    let data: String = read_to_string(path.as_ref())?;
+
+    Ok(Self {
+      mode: Mode::Replay,
+      path: path.as_ref().to_owned(),
+      interactions: Mutex::new(from_str(&data)?),
+    })
+  }
+
+  /// Records with `inner` if `COINBASE_CASSETTE` is set to `record`,
+  /// otherwise replays the cassette at `path`.
+  pub fn from_env<P: AsRef<Path>, T: Transport + 'static>(path: P, inner: T) -> Result<Self, Error> {
+    if var(ENV_CASSETTE).as_deref() == Ok("record") {
+      Ok(Self::record(path, inner))
+    } else {
+      Self::replay(path)
+    }
+  }
+
+  /// Returns the path of the cassette file.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Returns `true` if every recorded interaction has been replayed.
+  pub fn is_exhausted(&self) -> bool {
+    self
+      .interactions
+      .lock()
+      .expect("Poisoned Lock")
+      .iter()
+      .all(|interaction| interaction.played)
+  }
+}
+
+impl Transport for Cassette {
+  fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+    let mut interactions = self.interactions.lock().expect("Poisoned Lock");
+
+    match &self.mode {
+      Mode::Record(inner) => {
+        let response: HttpResponse = inner.send(request)?;
+
+        interactions.push(Interaction {
+          request: RecordedRequest {
+            method: request.method.to_string(),
+            url: target(request),
+            headers: headers(&request.headers),
+            body: request.body.clone(),
+          },
+          response: RecordedResponse {
+            status: response.status.as_u16(),
+            headers: headers(&response.headers),
+            body: response.body.clone(),
+          },
+          played: true,
+        });
+
+        write(&self.path, to_string_pretty(&*interactions)?)?;
+
+        Ok(response)
+      }
+      Mode::Replay => {
+        let interaction: &mut Interaction = interactions
+          .iter_mut()
+          .find(|interaction| interaction.matches(request))
+          .ok_or_else(|| Error::Unmatched {
+            method: request.method.clone(),
+            url: request.url.clone(),
+          })?;
+
+        interaction.played = true;
+
+        let mut response: HttpResponse = HttpResponse::new(
+          StatusCode::from_u16(interaction.response.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+          &interaction.response.body,
+        );
+
+        for (name, value) in interaction.response.headers.iter() {
+          if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value)) {
+            response.headers.insert(name, value);
+          }
+        }
+
+        Ok(response)
+      }
+    }
+  }
+}
+
+fn target(request: &HttpRequest) -> String {
+  match request.url.query() {
+    Some(query) => format!("{}?{}", request.url.path(), query),
+    None => request.url.path().to_owned(),
+  }
+}
+
+fn headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+  headers
+    .iter()
+    .filter(|(name, _)| !SCRUBBED.contains(&name.as_str()))
+    .filter_map(|(name, value)| value.to_str().ok().map(|value| (name.to_string(), value.to_owned())))
+    .collect()
+}
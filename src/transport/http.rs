@@ -0,0 +1,48 @@
+use reqwest::Body;
+use reqwest::Client as Http;
+use reqwest::Request;
+use reqwest::Response;
+
+use crate::error::Error;
+use crate::transport::HttpRequest;
+use crate::transport::HttpResponse;
+use crate::transport::Transport;
+
+///
+/// A `Transport` backed by a `reqwest` client.
+///
+#[derive(Debug)]
+pub struct HttpTransport {
+  http: Http,
+}
+
+impl Default for HttpTransport {
+  fn default() -> Self {
+    Self::new(Http::new())
+  }
+}
+
+impl HttpTransport {
+  pub fn new(http: Http) -> Self {
+    Self { http }
+  }
+}
+
+impl Transport for HttpTransport {
+  fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+    let prepared: Request = self
+      .http
+      .request(request.method.clone(), request.url.clone())
+      .headers(request.headers.clone())
+      .body(Body::from(request.body.clone()))
+      .build()?;
+
+    let mut response: Response = self.http.execute(prepared)?;
+
+    Ok(HttpResponse {
+      status: response.status(),
+      headers: response.headers().clone(),
+      body: response.text()?,
+    })
+  }
+}
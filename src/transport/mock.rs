@@ -0,0 +1,128 @@
+use reqwest::Method;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::error::Error;
+use crate::transport::HttpRequest;
+use crate::transport::HttpResponse;
+use crate::transport::Transport;
+
+const BASE_PATH: &str = "/v2/";
+
+#[derive(Debug)]
+struct Route {
+  method: Method,
+  path: String,
+  query: Option<String>,
+  responses: VecDeque<HttpResponse>,
+}
+
+impl Route {
+  fn matches(&self, base: &str, request: &HttpRequest) -> bool {
+    self.method == request.method
+      && request.url.path().strip_prefix(base) == Some(self.path.as_str())
+      && (self.query.is_none() || request.url.query() == self.query.as_deref())
+  }
+}
+
+///
+/// An in-memory `Transport` that serves canned responses.
+///
+/// Routes are matched by method and full path relative to the base path
+/// (`/v2/` unless set with `base_path`), e.g. `prices/BTC-USD/spot`; the
+/// query string is only compared if the route includes one. Registering
+/// several responses for the same route serves them in order, repeating the
+/// last one.
+///
+/// ```
+/// # use coinbase::prelude::*;
+/// # use coinbase::transport::*;
+/// let mock = MockTransport::new();
+///
+/// mock.mock(Method::GET, "time", HttpResponse::json(200, r#"{"data":{"iso":"2020-01-01T00:00:00Z","epoch":1577836800}}"#));
+///
+/// let client = Client::builder().transport(mock).build().unwrap();
+///
+/// assert_eq!(client.time().unwrap().data.epoch, 1577836800.0);
+/// ```
+///
+#[derive(Debug)]
+pub struct MockTransport {
+  base: String,
+  routes: Mutex<Vec<Route>>,
+  requests: Mutex<Vec<HttpRequest>>,
+}
+
+impl Default for MockTransport {
+  fn default() -> Self {
+    Self {
+      base: BASE_PATH.to_owned(),
+      routes: Default::default(),
+      requests: Default::default(),
+    }
+  }
+}
+
+impl MockTransport {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Sets the path of the client base URL that routes are relative to.
+  pub fn base_path(mut self, base: &str) -> Self {
+    self.base = format!("/{}/", base.trim_matches('/')).replace("//", "/");
+    self
+  }
+
+  /// Registers a `response` for requests matching `method` and `path`.
+  pub fn mock(&self, method: Method, path: &str, response: HttpResponse) -> &Self {
+    let mut parts = path.trim_start_matches('/').splitn(2, '?');
+    let path: String = parts.next().unwrap_or_default().to_owned();
+    let query: Option<String> = parts.next().map(ToOwned::to_owned);
+
+    let mut routes = self.routes.lock().expect("Poisoned Lock");
+
+    let existing = routes
+      .iter_mut()
+      .find(|route| route.method == method && route.path == path && route.query == query);
+
+    match existing {
+      Some(route) => route.responses.push_back(response),
+      None => routes.push(Route {
+        method,
+        path,
+        query,
+        responses: vec![response].into(),
+      }),
+    }
+
+    self
+  }
+
+  /// Returns all requests received so far.
+  pub fn requests(&self) -> Vec<HttpRequest> {
+    self.requests.lock().expect("Poisoned Lock").clone()
+  }
+}
+
+impl Transport for MockTransport {
+  fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+    self.requests.lock().expect("Poisoned Lock").push(request.clone());
+
+    let mut routes = self.routes.lock().expect("Poisoned Lock");
+
+    let route: &mut Route = routes
+      .iter_mut()
+      .find(|route| route.matches(&self.base, request))
+      .ok_or_else(|| Error::Unmatched {
+        method: request.method.clone(),
+        url: request.url.clone(),
+      })?;
+
+    if route.responses.len() > 1 {
+      Ok(route.responses.pop_front().expect("Empty Route"))
+    } else {
+      Ok(route.responses[0].clone())
+    }
+  }
+}
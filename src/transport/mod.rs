@@ -0,0 +1,78 @@
+mod cassette;
+mod http;
+mod mock;
+
+pub use self::cassette::Cassette;
+pub use self::cassette::ENV_CASSETTE;
+pub use self::http::HttpTransport;
+pub use self::mock::MockTransport;
+pub use reqwest::header::HeaderMap;
+pub use reqwest::Method;
+pub use reqwest::StatusCode;
+pub use reqwest::Url;
+
+use reqwest::header::HeaderValue;
+use reqwest::header::CONTENT_TYPE;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::error::Error;
+
+///
+/// A prepared (and signed) API request.
+///
+#[derive(Clone, Debug)]
+pub struct HttpRequest {
+  pub method: Method,
+  pub url: Url,
+  pub headers: HeaderMap,
+  pub body: String,
+}
+
+///
+/// A raw API response.
+///
+#[derive(Clone, Debug)]
+pub struct HttpResponse {
+  pub status: StatusCode,
+  pub headers: HeaderMap,
+  pub body: String,
+}
+
+impl HttpResponse {
+  pub fn new(status: StatusCode, body: &str) -> Self {
+    Self {
+      status,
+      headers: HeaderMap::new(),
+      body: body.to_owned(),
+    }
+  }
+
+  /// Creates a response with a JSON `body`.
+  pub fn json(status: u16, body: &str) -> Self {
+    let status: StatusCode = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    let mut this: Self = Self::new(status, body);
+
+    this
+      .headers
+      .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+
+    this
+  }
+}
+
+///
+/// Sends prepared requests to the API.
+///
+/// The default transport uses `reqwest`; `MockTransport` serves canned
+/// responses from memory.
+///
+pub trait Transport: Debug + Send + Sync {
+  fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error>;
+}
+
+impl<T: Transport + ?Sized> Transport for Arc<T> {
+  fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+    (**self).send(request)
+  }
+}
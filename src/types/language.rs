@@ -1,14 +1,18 @@
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result;
+use std::str::FromStr;
+
+use crate::error::Error;
 
 //
 // https://developers.coinbase.com/api/v2#localization
 //
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub enum Language {
   DE,
+  #[default]
   EN,
   ES,
   ESMX,
@@ -20,12 +24,6 @@ pub enum Language {
   PTBR,
 }
 
-impl Default for Language {
-  fn default() -> Self {
-    Language::EN
-  }
-}
-
 impl Display for Language {
   fn fmt(&self, f: &mut Formatter) -> Result {
     match *self {
@@ -42,3 +40,23 @@ impl Display for Language {
     }
   }
 }
+
+impl FromStr for Language {
+  type Err = Error;
+
+  fn from_str(string: &str) -> ::std::result::Result<Self, Self::Err> {
+    match string.to_lowercase().replace('_', "-").as_str() {
+      "de" => Ok(Language::DE),
+      "en" => Ok(Language::EN),
+      "es" => Ok(Language::ES),
+      "es-mx" => Ok(Language::ESMX),
+      "fr" => Ok(Language::FR),
+      "id" => Ok(Language::ID),
+      "it" => Ok(Language::IT),
+      "nl" => Ok(Language::NL),
+      "pt" => Ok(Language::PT),
+      "pt-br" => Ok(Language::PTBR),
+      _ => Err(Error::Config(format!("Unknown language `{}`", string))),
+    }
+  }
+}
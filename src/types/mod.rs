@@ -4,6 +4,7 @@ mod language;
 mod money;
 mod pagination;
 mod public;
+mod request;
 mod resource;
 mod response;
 mod wallet;
@@ -20,6 +21,9 @@ pub use self::pagination::Pagination;
 pub use self::public::Currency;
 pub use self::public::Rates;
 pub use self::public::Time;
+pub use self::request::BuyRequest;
+pub use self::request::SendMoney;
+pub use self::request::TransferMoney;
 pub use self::resource::ResourceMeta;
 pub use self::resource::ResourceRef;
 pub use self::resource::ResourceType;
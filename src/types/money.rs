@@ -1,6 +1,6 @@
 use crate::deserialize_f64;
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Money {
   #[serde(deserialize_with = "deserialize_f64")]
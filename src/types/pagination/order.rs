@@ -1,13 +1,8 @@
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 #[serde(rename_all = "lowercase")]
 pub enum Order {
   ASC,
+  #[default]
   DESC,
 }
-
-impl Default for Order {
-  fn default() -> Self {
-    Order::DESC
-  }
-}
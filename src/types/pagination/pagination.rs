@@ -1,6 +1,6 @@
 use crate::types::Order;
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Pagination {
   pub limit: usize,
@@ -1,6 +1,6 @@
 use crate::types::DateTime;
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Time {
   pub iso: DateTime,
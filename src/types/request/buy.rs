@@ -0,0 +1,67 @@
+use crate::types::request::idem;
+
+///
+/// Parameters for placing a buy order.
+///
+/// Placing a buy again with the same idempotency token (see `with_idem`)
+/// returns the original buy instead of placing a second one.
+///
+/// https://developers.coinbase.com/api/v2#place-buy-order
+///
+#[derive(Clone, Debug, Serialize)]
+pub struct BuyRequest {
+  amount: String,
+  currency: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  payment_method: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  agree_btc_amount_varies: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  commit: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  quote: Option<bool>,
+  idem: String,
+}
+
+impl BuyRequest {
+  /// Buys `amount` (without fees) denominated in `currency`, which may be
+  /// the account currency or a fiat currency.
+  pub fn new(amount: &str, currency: &str) -> Self {
+    Self {
+      amount: amount.to_owned(),
+      currency: currency.to_owned(),
+      payment_method: None,
+      agree_btc_amount_varies: None,
+      commit: None,
+      quote: None,
+      idem: idem(),
+    }
+  }
+
+  /// Sets the payment method to pay with (defaults to the primary one).
+  pub fn payment_method(mut self, payment_method: &str) -> Self {
+    self.payment_method = Some(payment_method.to_owned());
+    self
+  }
+
+  /// Sets whether the buy may go through if the amount bought changes.
+  pub fn agree_btc_amount_varies(mut self, agree: bool) -> Self {
+    self.agree_btc_amount_varies = Some(agree);
+    self
+  }
+
+  /// Sets whether the buy is committed immediately (defaults to true); see
+  /// `Client::commit_buy`.
+  pub fn commit(mut self, commit: bool) -> Self {
+    self.commit = Some(commit);
+    self
+  }
+
+  /// Only returns a quote of the buy without placing it.
+  pub fn quote(mut self, quote: bool) -> Self {
+    self.quote = Some(quote);
+    self
+  }
+}
+
+idem!(BuyRequest);
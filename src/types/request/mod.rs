@@ -0,0 +1,13 @@
+mod buy;
+mod send;
+mod transfer;
+
+pub use self::buy::BuyRequest;
+pub use self::send::SendMoney;
+pub use self::transfer::TransferMoney;
+
+use uuid::Uuid;
+
+pub(crate) fn idem() -> String {
+  Uuid::new_v4().to_string()
+}
@@ -0,0 +1,67 @@
+use crate::types::request::idem;
+
+///
+/// Parameters for sending funds to a crypto address or email address.
+///
+/// A random idempotency token (`idem`) is generated when the request is
+/// created. Persist it before sending; if the send fails with a network
+/// error, replaying the request with the same token (see `with_idem`)
+/// guarantees the funds are sent at most once.
+///
+/// https://developers.coinbase.com/api/v2#send-money
+///
+#[derive(Clone, Debug, Serialize)]
+pub struct SendMoney {
+  #[serde(rename = "type")]
+  kind: &'static str,
+  to: String,
+  amount: String,
+  currency: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  skip_notifications: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  fee: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  destination_tag: Option<String>,
+  idem: String,
+}
+
+impl SendMoney {
+  pub fn new(to: &str, amount: &str, currency: &str) -> Self {
+    Self {
+      kind: "send",
+      to: to.to_owned(),
+      amount: amount.to_owned(),
+      currency: currency.to_owned(),
+      description: None,
+      skip_notifications: None,
+      fee: None,
+      destination_tag: None,
+      idem: idem(),
+    }
+  }
+
+  pub fn description(mut self, description: &str) -> Self {
+    self.description = Some(description.to_owned());
+    self
+  }
+
+  pub fn skip_notifications(mut self, skip: bool) -> Self {
+    self.skip_notifications = Some(skip);
+    self
+  }
+
+  pub fn fee(mut self, fee: &str) -> Self {
+    self.fee = Some(fee.to_owned());
+    self
+  }
+
+  pub fn destination_tag(mut self, tag: &str) -> Self {
+    self.destination_tag = Some(tag.to_owned());
+    self
+  }
+}
+
+idem!(SendMoney);
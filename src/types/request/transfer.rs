@@ -0,0 +1,41 @@
+use crate::types::request::idem;
+
+///
+/// Parameters for transferring funds between two of the user's accounts.
+///
+/// Carries a random idempotency token, so a transfer retried after a
+/// network error with the same token (see `with_idem`) moves funds once.
+///
+/// https://developers.coinbase.com/api/v2#transfer-money-between-accounts
+///
+#[derive(Clone, Debug, Serialize)]
+pub struct TransferMoney {
+  #[serde(rename = "type")]
+  kind: &'static str,
+  to: String,
+  amount: String,
+  currency: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  description: Option<String>,
+  idem: String,
+}
+
+impl TransferMoney {
+  pub fn new(to: &str, amount: &str, currency: &str) -> Self {
+    Self {
+      kind: "transfer",
+      to: to.to_owned(),
+      amount: amount.to_owned(),
+      currency: currency.to_owned(),
+      description: None,
+      idem: idem(),
+    }
+  }
+
+  pub fn description(mut self, description: &str) -> Self {
+    self.description = Some(description.to_owned());
+    self
+  }
+}
+
+idem!(TransferMoney);
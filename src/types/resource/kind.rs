@@ -1,4 +1,4 @@
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ResourceType {
   Account,
@@ -11,12 +11,7 @@ pub enum ResourceType {
   Transaction,
   User,
   Withdrawal,
+  #[default]
   #[serde(other)]
   Unknown,
 }
-
-impl Default for ResourceType {
-  fn default() -> Self {
-    ResourceType::Unknown
-  }
-}
@@ -1,7 +1,8 @@
 use crate::types::DateTime;
 use crate::types::ResourceType;
+use crate::types::UtcDate;
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ResourceMeta {
   pub id: String,
@@ -10,3 +11,10 @@ pub struct ResourceMeta {
   pub created_at: Option<DateTime>,
   pub updated_at: Option<DateTime>,
 }
+
+impl ResourceMeta {
+  /// Returns the creation time, or the Unix epoch if it is missing.
+  pub fn created(&self) -> UtcDate {
+    self.created_at.as_ref().map(|time| **time).unwrap_or_default()
+  }
+}
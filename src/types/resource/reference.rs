@@ -1,6 +1,6 @@
 use crate::types::ResourceType;
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ResourceRef {
   pub id: String,
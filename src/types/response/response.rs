@@ -1,8 +1,9 @@
+use crate::error::Error;
 use crate::types::ErrorMessage;
 use crate::types::Pagination;
 use crate::types::WarningMessage;
 
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
 pub struct Response<T: Default> {
@@ -11,3 +12,14 @@ pub struct Response<T: Default> {
   pub errors: Vec<ErrorMessage>,
   pub warnings: Vec<WarningMessage>,
 }
+
+impl<T: Default> Response<T> {
+  /// Returns the data of the response, or `Error::API` when it reports errors.
+  pub fn into_result(self) -> Result<T, Error> {
+    if self.errors.is_empty() {
+      Ok(self.data)
+    } else {
+      Err(Error::API(self.errors))
+    }
+  }
+}
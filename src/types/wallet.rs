@@ -8,26 +8,21 @@ use crate::types::ValueMap;
 ///
 /// TODO
 ///
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AccountType {
   Wallet,
   Fiat,
   Vault,
+  #[default]
   #[serde(other)]
   Unknown,
 }
 
-impl Default for AccountType {
-  fn default() -> Self {
-    AccountType::Unknown
-  }
-}
-
 ///
 /// TODO
 ///
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub enum PaymentMethodType {
   #[serde(rename = "ach_bank_account")]
   ACHBankAccount,
@@ -47,40 +42,30 @@ pub enum PaymentMethodType {
   EFTBankAccount,
   #[serde(rename = "interac")]
   Interac,
+  #[default]
   #[serde(other)]
   Unknown,
 }
 
-impl Default for PaymentMethodType {
-  fn default() -> Self {
-    PaymentMethodType::Unknown
-  }
-}
-
 ///
 /// TODO
 ///
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TransferStatus {
   Created,
   Completed,
   Canceled,
+  #[default]
   #[serde(other)]
   Unknown,
 }
 
-impl Default for TransferStatus {
-  fn default() -> Self {
-    TransferStatus::Unknown
-  }
-}
-
 ///
 /// TODO
 ///
-#[derive(Clone, Debug, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TransactionStatus {
   Pending,
   Completed,
@@ -89,21 +74,16 @@ pub enum TransactionStatus {
   Canceled,
   WaitingForSignature,
   WaitingForClearing,
+  #[default]
   #[serde(other)]
   Unknown,
 }
 
-impl Default for TransactionStatus {
-  fn default() -> Self {
-    TransactionStatus::Unknown
-  }
-}
-
 ///
 /// TODO
 ///
-#[derive(Clone, Debug, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TransactionType {
   Send,
   Request,
@@ -115,20 +95,15 @@ pub enum TransactionType {
   ExchangeDeposit,
   ExchangeWithdrawal,
   VaultWithdrawal,
+  #[default]
   #[serde(other)]
   Unknown,
 }
 
-impl Default for TransactionType {
-  fn default() -> Self {
-    TransactionType::Unknown
-  }
-}
-
 ///
 /// TODO
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct AccountCurrency {
   #[serde(rename = "type")]
@@ -150,7 +125,7 @@ pub struct AccountCurrency {
 ///
 /// TODO
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct UserCountry {
   pub code: String, // TODO: Enum
@@ -164,7 +139,7 @@ pub struct UserCountry {
 ///
 /// TODO
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct UserAuth {
   pub method: String,
@@ -175,7 +150,7 @@ pub struct UserAuth {
 ///
 /// https://developers.coinbase.com/api/v2#accounts
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Account {
   #[serde(flatten)]
@@ -196,7 +171,7 @@ pub struct Account {
 ///
 /// https://developers.coinbase.com/api/v2#addresses
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Address {
   #[serde(flatten)]
@@ -216,7 +191,7 @@ pub struct Address {
 ///
 /// https://developers.coinbase.com/api/v2#notifications
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Notification {
   #[serde(flatten)]
@@ -232,7 +207,7 @@ pub struct Notification {
 ///
 /// https://developers.coinbase.com/api/v2#payment-method
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct PaymentMethod {
   #[serde(flatten)]
@@ -255,7 +230,7 @@ pub struct PaymentMethod {
 ///
 /// https://developers.coinbase.com/api/v2#transactions
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Transaction {
   #[serde(flatten)]
@@ -283,7 +258,7 @@ pub struct Transaction {
 ///
 /// https://developers.coinbase.com/api/v2#users
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct User {
   #[serde(flatten)]
@@ -315,7 +290,7 @@ pub struct User {
 ///
 /// https://developers.coinbase.com/api/v2#buys
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Buy {
   #[serde(flatten)]
@@ -345,7 +320,7 @@ pub struct Buy {
 ///
 /// https://developers.coinbase.com/api/v2#sells
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Sell {
   #[serde(flatten)]
@@ -365,7 +340,7 @@ pub struct Sell {
 ///
 /// https://developers.coinbase.com/api/v2#deposits
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Deposit {
   #[serde(flatten)]
@@ -383,7 +358,7 @@ pub struct Deposit {
 ///
 /// https://developers.coinbase.com/api/v2#withdrawals
 ///
-#[derive(Clone, Debug, Default, Deserialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Withdrawal {
   #[serde(flatten)]
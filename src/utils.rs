@@ -1,9 +1,21 @@
 use chrono::Utc;
+use hmac::Hmac;
+use hmac::Mac;
+use reqwest::Url;
+use rust_decimal::Decimal;
 use serde::de::Deserializer;
 use serde::de::Error;
 use serde::de::Visitor;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
 use std::fmt::Formatter;
 use std::fmt::Result as FResult;
+use std::str::FromStr;
+
+use crate::error::Error as CBError;
+
+type HmacSha = Hmac<Sha256>;
 
 struct F64;
 
@@ -27,6 +39,67 @@ pub(crate) fn timestamp() -> i64 {
   Utc::now().timestamp()
 }
 
+/// Returns the hex-encoded HMAC-SHA256 signature of `message`.
+pub(crate) fn sign(secret: &str, message: &str) -> Result<String, CBError> {
+  let apply = |mut hmac: HmacSha| {
+    hmac.input(message.as_bytes());
+    hmac
+  };
+
+  let hmac: HmacSha = Hmac::new_varkey(secret.as_bytes()).map(apply)?;
+  let signed: String = format!("{:x}", hmac.result().code());
+
+  Ok(signed)
+}
+
 pub(crate) fn deserialize_f64<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
   deserializer.deserialize_any(F64)
 }
+
+/// Appends a relative `path` (with an optional query string) to `base`.
+///
+/// Path segments are percent-encoded and `.`/`..` segments are dropped, so
+/// the resulting URL can never escape the base URL.
+pub(crate) fn join_url(base: &Url, path: &str) -> Result<Url, CBError> {
+  let mut url: Url = base.clone();
+  let mut parts = path.splitn(2, '?');
+
+  url
+    .path_segments_mut()
+    .map_err(|_| CBError::Config(format!("Invalid base URL: {}", base)))?
+    .pop_if_empty()
+    .extend(parts.next().unwrap_or_default().split('/'));
+
+  url.set_query(parts.next());
+
+  Ok(url)
+}
+
+/// Returns the id of the first API error in a response body, if any.
+pub(crate) fn error_id(body: &str) -> Option<String> {
+  serde_json::from_str::<serde_json::Value>(body)
+    .ok()?
+    .get("errors")?
+    .get(0)?
+    .get("id")?
+    .as_str()
+    .map(ToOwned::to_owned)
+}
+
+/// Converts an API amount to a `Decimal`.
+///
+/// Amounts are parsed from decimal strings into the shortest `f64` that
+/// round-trips, so formatting them back yields the original digits.
+pub(crate) fn decimal(amount: f64) -> Decimal {
+  Decimal::from_str(&amount.to_string())
+    .or_else(|_| Decimal::from_scientific(&format!("{:e}", amount)))
+    .unwrap_or_default()
+}
+
+/// Returns the serialized name of a unit enum variant.
+pub(crate) fn name<T: Serialize>(value: &T) -> String {
+  match serde_json::to_value(value) {
+    Ok(Value::String(name)) => name,
+    _ => String::new(),
+  }
+}
@@ -0,0 +1,177 @@
+use serde_json::Value;
+use std::thread::sleep;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::client::Client;
+use crate::error::Error;
+use crate::types::Transaction;
+use crate::types::TransactionStatus;
+
+///
+/// Options of `Client::watch_transaction`.
+///
+/// The transaction is polled every `interval`; the delay doubles after each
+/// poll that sees no change, up to `max_interval`, and is reset when the
+/// transaction changes.
+///
+#[derive(Clone, Debug)]
+pub struct Watcher {
+  interval: Duration,
+  max_interval: Duration,
+  timeout: Duration,
+  confirmations: Option<u64>,
+}
+
+impl Default for Watcher {
+  fn default() -> Self {
+    Self {
+      interval: Duration::from_secs(1),
+      max_interval: Duration::from_secs(30),
+      timeout: Duration::from_secs(10 * 60),
+      confirmations: None,
+    }
+  }
+}
+
+impl Watcher {
+  pub fn new() -> Self {
+    Default::default()
+  }
+
+  /// Sets the delay after the first poll and after each change (defaults to 1s).
+  pub fn interval(mut self, interval: Duration) -> Self {
+    self.interval = interval;
+    self
+  }
+
+  /// Sets the longest delay between polls (defaults to 30s).
+  pub fn max_interval(mut self, max_interval: Duration) -> Self {
+    self.max_interval = max_interval;
+    self
+  }
+
+  /// Sets how long to watch before giving up (defaults to 10 minutes).
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = timeout;
+    self
+  }
+
+  /// Keeps watching a completed transaction until `network.confirmations`
+  /// reaches `confirmations`. Transactions without network details finish
+  /// when they complete.
+  pub fn confirmations(mut self, confirmations: u64) -> Self {
+    self.confirmations = Some(confirmations);
+    self
+  }
+
+  fn delay(&self, unchanged: u32) -> Duration {
+    self
+      .interval
+      .checked_mul(1 << unchanged.min(16))
+      .unwrap_or(self.max_interval)
+      .min(self.max_interval)
+  }
+
+  fn is_done(&self, transaction: &Transaction) -> bool {
+    match transaction.status {
+      TransactionStatus::Completed => match (self.confirmations, confirmations(transaction)) {
+        (Some(required), Some(current)) => current >= required,
+        _ => true,
+      },
+      _ => is_terminal(&transaction.status),
+    }
+  }
+}
+
+///
+/// The last state of a watched transaction.
+///
+#[derive(Clone, Debug)]
+pub struct Watched {
+  pub transaction: Transaction,
+  /// Number of `get_transaction` calls made.
+  pub polls: u32,
+  pub elapsed: Duration,
+  /// `true` if the timeout passed before the transaction finished.
+  pub timed_out: bool,
+}
+
+impl Client {
+  /// Polls a transaction until its status is terminal (`Completed`, `Failed`,
+  /// `Expired` or `Canceled`) or the watcher times out.
+  ///
+  /// `callback` is called with the first state seen and again whenever the
+  /// status or the number of network confirmations changes.
+  ///
+  /// ```no_run
+  /// # use coinbase::prelude::*;
+  /// # use coinbase::watch::Watcher;
+  /// # fn run(client: &Client, account: &str, transaction: &str) -> Result<(), Error> {
+  /// let watched = client.watch_transaction(account, transaction, &Watcher::new(), |transaction| {
+  ///   println!("{:?}", transaction.status);
+  /// })?;
+  ///
+  /// assert!(!watched.timed_out);
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn watch_transaction<F: FnMut(&Transaction)>(
+    &self,
+    account: &str,
+    transaction: &str,
+    watcher: &Watcher,
+    mut callback: F,
+  ) -> Result<Watched, Error> {
+    let start: Instant = Instant::now();
+    let mut previous: Option<(TransactionStatus, Option<u64>)> = None;
+    let mut unchanged: u32 = 0;
+    let mut polls: u32 = 0;
+
+    loop {
+      let current: Transaction = self.get_transaction(account, transaction)?.into_result()?;
+      let state: (TransactionStatus, Option<u64>) = (current.status.clone(), confirmations(&current));
+
+      polls += 1;
+
+      if previous.as_ref() != Some(&state) {
+        callback(&current);
+        previous = Some(state);
+        unchanged = 0;
+      } else {
+        unchanged += 1;
+      }
+
+      let elapsed: Duration = start.elapsed();
+      let done: bool = watcher.is_done(&current);
+
+      if done || elapsed >= watcher.timeout {
+        return Ok(Watched {
+          transaction: current,
+          polls,
+          elapsed,
+          timed_out: !done,
+        });
+      }
+
+      sleep(watcher.delay(unchanged).min(watcher.timeout - elapsed));
+    }
+  }
+}
+
+/// Returns `true` if a transaction with `status` will not change again.
+pub fn is_terminal(status: &TransactionStatus) -> bool {
+  matches!(
+    status,
+    TransactionStatus::Completed | TransactionStatus::Failed | TransactionStatus::Expired | TransactionStatus::Canceled
+  )
+}
+
+/// Returns the number of network confirmations of a transaction, if known.
+pub fn confirmations(transaction: &Transaction) -> Option<u64> {
+  match transaction.network.as_ref()?.get("confirmations")? {
+    Value::Number(number) => number.as_u64(),
+    Value::String(number) => number.parse().ok(),
+    _ => None,
+  }
+}
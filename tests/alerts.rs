@@ -0,0 +1,180 @@
+extern crate chrono;
+extern crate coinbase;
+extern crate rust_decimal;
+
+use chrono::Duration as Span;
+use chrono::Utc;
+use coinbase::alerts::Alert;
+use coinbase::alerts::AlertEngine;
+use coinbase::alerts::PriceSource;
+use coinbase::alerts::Rule;
+use coinbase::alerts::RuleId;
+use coinbase::prelude::UtcDate;
+use coinbase::server::MockServer;
+use rust_decimal::Decimal;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::channel;
+use std::sync::mpsc::Receiver;
+use std::sync::mpsc::Sender;
+use std::thread;
+use std::time::Duration;
+
+fn fired(engine: &mut AlertEngine, prices: &[i64]) -> Vec<Vec<RuleId>> {
+  let start: UtcDate = Utc::now();
+
+  prices
+    .iter()
+    .enumerate()
+    .map(|(index, price)| {
+      engine
+        .evaluate(
+          "btc",
+          "usd",
+          PriceSource::Spot,
+          Decimal::from(*price),
+          start + Span::seconds(index as i64),
+        )
+        .into_iter()
+        .map(|alert| alert.rule)
+        .collect()
+    })
+    .collect()
+}
+
+#[test]
+fn test_hysteresis() {
+  let mut engine: AlertEngine = AlertEngine::new();
+  let above: RuleId = engine.add(Rule::above("BTC", "USD", Decimal::from(100)).hysteresis(Decimal::from(5)));
+  let below: RuleId = engine.add(Rule::below("BTC", "USD", Decimal::from(90)));
+
+  engine.add(Rule::above("BTC", "USD", Decimal::from(100)).source(PriceSource::Buy));
+  engine.add(Rule::above("ETH", "USD", Decimal::from(100)));
+
+  assert_eq!(
+    fired(&mut engine, &[95, 101, 102, 97, 101, 94, 100, 89, 91, 90]),
+    vec![
+      vec![],
+      vec![above],
+      vec![],
+      vec![],
+      vec![],
+      vec![],
+      vec![above],
+      vec![below],
+      vec![],
+      vec![below],
+    ]
+  );
+}
+
+#[test]
+fn test_initial_state() {
+  let mut engine: AlertEngine = AlertEngine::new();
+  let above: RuleId = engine.add(Rule::above("BTC", "USD", Decimal::from(100)));
+
+  assert_eq!(
+    fired(&mut engine, &[110, 120, 99, 100]),
+    vec![vec![], vec![], vec![], vec![above]]
+  );
+  assert!(engine.remove(above).is_some());
+  assert_eq!(engine.rules().count(), 0);
+}
+
+#[test]
+fn test_change() {
+  let mut engine: AlertEngine = AlertEngine::new();
+  let rise: RuleId = engine.add(Rule::change("BTC", "USD", Decimal::from(10), Duration::from_secs(2)));
+  let drop: RuleId = engine.add(Rule::change("BTC", "USD", Decimal::from(-5), Duration::from_secs(2)));
+
+  assert_eq!(
+    fired(&mut engine, &[100, 104, 110, 120, 121, 121, 114]),
+    vec![vec![], vec![], vec![rise], vec![], vec![], vec![], vec![drop]]
+  );
+
+  let mut engine: AlertEngine = AlertEngine::new();
+  let rise: RuleId = engine.add(Rule::change("BTC", "USD", Decimal::from(10), Duration::from_secs(2)));
+  let start: UtcDate = Utc::now();
+
+  engine.evaluate("BTC", "USD", PriceSource::Spot, Decimal::from(200), start);
+
+  let alerts: Vec<Alert> = engine.evaluate(
+    "BTC",
+    "USD",
+    PriceSource::Spot,
+    Decimal::from(150),
+    start + Span::seconds(1),
+  );
+
+  assert!(alerts.is_empty());
+
+  let alerts: Vec<Alert> = engine.evaluate(
+    "BTC",
+    "USD",
+    PriceSource::Spot,
+    Decimal::from(250),
+    start + Span::seconds(2),
+  );
+
+  assert_eq!(alerts.len(), 1);
+  assert_eq!(alerts[0].rule, rise);
+  assert_eq!(alerts[0].change, Some(Decimal::from(25)));
+  assert_eq!(alerts[0].time, start + Span::seconds(2));
+}
+
+#[test]
+fn test_change_first_interval() {
+  let mut engine: AlertEngine = AlertEngine::new();
+  let drop: RuleId = engine.add(Rule::change("BTC", "USD", Decimal::from(-5), Duration::from_secs(2)));
+
+  assert_eq!(fired(&mut engine, &[100, 94, 93]), vec![vec![], vec![drop], vec![]]);
+}
+
+#[test]
+fn test_poll() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let mut engine: AlertEngine = AlertEngine::new();
+
+  server.state().set_price("BTC", "USD", 9000.0);
+
+  let rule: RuleId = engine.add(Rule::above("BTC", "USD", Decimal::from(10000)));
+
+  engine.add(Rule::below("BTC", "USD", Decimal::from(8000)).source(PriceSource::Sell));
+
+  assert!(engine.poll(&client).unwrap().is_empty());
+
+  server.state().set_price("BTC", "USD", 10500.0);
+
+  let alerts: Vec<Alert> = engine.poll(&client).unwrap();
+
+  assert_eq!(alerts.len(), 1);
+  assert_eq!(alerts[0].rule, rule);
+  assert_eq!(alerts[0].price, Decimal::from(10500));
+}
+
+#[test]
+fn test_run_channel() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let stop: AtomicBool = AtomicBool::new(false);
+  let (sender, receiver): (Sender<Alert>, Receiver<Alert>) = channel();
+  let mut engine: AlertEngine = AlertEngine::new().interval(Duration::from_millis(10));
+
+  server.state().set_price("BTC", "USD", 9000.0);
+  engine.add(Rule::below("BTC", "USD", Decimal::from(8000)));
+
+  thread::scope(|scope| {
+    let runner = scope.spawn(|| engine.run_channel(&client, &stop, sender));
+
+    thread::sleep(Duration::from_millis(50));
+    server.state().set_price("BTC", "USD", 7500.0);
+
+    let alert: Alert = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+
+    stop.store(true, Ordering::Relaxed);
+
+    assert_eq!(alert.price, Decimal::from(7500));
+    assert!(runner.join().unwrap().is_ok());
+  });
+}
@@ -0,0 +1,35 @@
+extern crate coinbase;
+
+use coinbase::prelude::*;
+use std::time::Duration;
+
+#[test]
+fn test_builder() {
+  let client = Client::builder()
+    .credentials("key", "secret")
+    .base_url("http://localhost:8080/v2")
+    .language(Language::FR)
+    .user_agent("my-service/1.0")
+    .version("2020-01-01")
+    .timeout(Duration::from_secs(5))
+    .connect_timeout(Duration::from_secs(1))
+    .build()
+    .unwrap();
+
+  assert_eq!(client.uagent, "my-service/1.0");
+  assert_eq!(client.version, "2020-01-01");
+  assert_eq!(client.language.to_string(), "fr");
+  assert!(format!("{:?}", client).contains("\"http://localhost:8080/v2/\""));
+}
+
+#[test]
+fn test_builder_invalid_base_url() {
+  assert!(matches!(
+    Client::builder().base_url("not a url").build(),
+    Err(Error::URL(_))
+  ));
+  assert!(matches!(
+    Client::builder().base_url("mailto:a@b.c").build(),
+    Err(Error::Config(_))
+  ));
+}
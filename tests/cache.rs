@@ -0,0 +1,135 @@
+extern crate coinbase;
+
+use coinbase::cache::Cache;
+use coinbase::prelude::*;
+use coinbase::transport::*;
+use std::sync::Arc;
+use std::thread::sleep;
+use std::time::Duration;
+
+const SPOT: &str = r#"{"data":{"base":"BTC","currency":"USD","amount":"7225.51"}}"#;
+const ETH: &str = r#"{"data":{"base":"ETH","currency":"USD","amount":"180.00"}}"#;
+const CURRENCIES: &str = r#"{"data":[{"id":"USD","name":"US Dollar","min_size":"0.01"}]}"#;
+const UNAVAILABLE: &str = r#"{"errors":[{"id":"internal_server_error","message":"Unavailable"}]}"#;
+
+fn client(mock: &Arc<MockTransport>, cache: Cache) -> Client {
+  Client::builder()
+    .transport(mock.clone())
+    .retry_policy(RetryPolicy::none())
+    .cache(cache)
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_cache_hits() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock.mock(Method::GET, "currencies", HttpResponse::json(200, CURRENCIES));
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+  mock.mock(Method::GET, "accounts", HttpResponse::json(200, r#"{"data":[]}"#));
+
+  let client = client(&mock, Cache::new());
+
+  for _ in 0..3 {
+    assert_eq!(client.currencies().unwrap().data[0].id, "USD");
+    assert_eq!(client.spot_price("BTC", "USD").unwrap().data.amount, 7225.51);
+    client.list_accounts().unwrap();
+  }
+
+  assert_eq!(mock.requests().len(), 5);
+  assert_eq!(client.cache().unwrap().len(), 2);
+}
+
+#[test]
+fn test_cache_expires() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  let client = client(
+    &mock,
+    Cache::empty().ttl("prices/{pair}/spot", Duration::from_millis(50)),
+  );
+
+  client.spot_price("BTC", "USD").unwrap();
+  client.spot_price("BTC", "USD").unwrap();
+  sleep(Duration::from_millis(60));
+  client.spot_price("BTC", "USD").unwrap();
+
+  assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn test_cache_stale_while_error() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT))
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(503, UNAVAILABLE));
+
+  let stale = client(
+    &mock,
+    Cache::empty()
+      .ttl("prices/{pair}/spot", Duration::from_millis(0))
+      .max_stale(Duration::from_secs(60)),
+  );
+
+  stale.spot_price("BTC", "USD").unwrap();
+
+  let response = stale.spot_price("BTC", "USD").unwrap();
+
+  assert_eq!(response.data.amount, 7225.51);
+  assert!(response.errors.is_empty());
+  assert_eq!(mock.requests().len(), 2);
+
+  let mock = Arc::new(MockTransport::new());
+
+  mock
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT))
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(503, UNAVAILABLE));
+
+  let fresh = client(
+    &mock,
+    Cache::empty()
+      .ttl("prices/{pair}/spot", Duration::from_millis(0))
+      .no_stale(),
+  );
+
+  fresh.spot_price("BTC", "USD").unwrap();
+
+  assert_eq!(
+    fresh.spot_price("BTC", "USD").unwrap().errors[0].id,
+    "internal_server_error"
+  );
+}
+
+#[test]
+fn test_cache_invalidate() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+  mock.mock(Method::GET, "prices/ETH-USD/spot", HttpResponse::json(200, ETH));
+  mock.mock(Method::GET, "currencies", HttpResponse::json(200, CURRENCIES));
+
+  let client = client(&mock, Cache::new());
+  let cache: &Cache = client.cache().unwrap();
+
+  client.spot_price("BTC", "USD").unwrap();
+  client.spot_price("ETH", "USD").unwrap();
+  client.currencies().unwrap();
+
+  assert_eq!(cache.len(), 3);
+
+  cache.invalidate("prices/BTC-USD/spot");
+  assert_eq!(cache.len(), 2);
+
+  client.spot_price("BTC", "USD").unwrap();
+  assert_eq!(mock.requests().len(), 4);
+
+  cache.invalidate("prices/{pair}/spot");
+  assert_eq!(cache.len(), 1);
+
+  cache.clear();
+  assert!(cache.is_empty());
+}
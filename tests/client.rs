@@ -1,36 +1,39 @@
 extern crate coinbase;
 
 use coinbase::prelude::*;
-use std::thread::sleep;
+use coinbase::transport::Cassette;
+use coinbase::transport::HttpTransport;
 use std::time::Duration;
 
-const TIMEOUT: u64 = 200;
-
-fn wait() {
-  sleep(Duration::from_millis(TIMEOUT))
+// Replays `tests/cassettes/<name>.json`; set `COINBASE_CASSETTE=record`
+// to record it against the live API instead.
+fn client(name: &str) -> Client {
+  let path: String = format!("{}/tests/cassettes/{}.json", env!("CARGO_MANIFEST_DIR"), name);
+  let cassette: Cassette = Cassette::from_env(path, HttpTransport::default()).unwrap();
+
+  Client::builder()
+    .transport(cassette)
+    .rate_limit(5, Duration::from_secs(1))
+    .build()
+    .unwrap()
 }
 
 #[test]
 fn test_time() {
-  wait();
-
-  let _ = Client::new().time().unwrap();
+  let _ = client("time").time().unwrap();
 }
 
 #[test]
+#[allow(clippy::len_zero)]
 fn test_currencies() {
-  wait();
-
-  let response = Client::new().currencies().unwrap();
+  let response = client("currencies").currencies().unwrap();
 
   assert!(response.data.len() > 0);
 }
 
 #[test]
 fn test_rates() {
-  wait();
-
-  let response = Client::new().rates(Some("EUR")).unwrap();
+  let response = client("rates").rates(Some("EUR")).unwrap();
 
   assert_eq!(response.data.currency, String::from("EUR"));
   assert!(response.data.rates.contains_key("BTC"));
@@ -40,9 +43,7 @@ fn test_rates() {
 
 #[test]
 fn test_buy_price() {
-  wait();
-
-  let response = Client::new().buy_price("ETH", "USD").unwrap();
+  let response = client("buy_price").buy_price("ETH", "USD").unwrap();
 
   assert_eq!(response.data.currency, String::from("USD"));
   assert_eq!(response.data.base, Some(String::from("ETH")));
@@ -50,9 +51,7 @@ fn test_buy_price() {
 
 #[test]
 fn test_sell_price() {
-  wait();
-
-  let response = Client::new().sell_price("LTC", "EUR").unwrap();
+  let response = client("sell_price").sell_price("LTC", "EUR").unwrap();
 
   assert_eq!(response.data.currency, String::from("EUR"));
   assert_eq!(response.data.base, Some(String::from("LTC")));
@@ -60,10 +59,31 @@ fn test_sell_price() {
 
 #[test]
 fn test_spot_price() {
-  wait();
-
-  let response = Client::new().spot_price("BTC", "USD").unwrap();
+  let response = client("spot_price").spot_price("BTC", "USD").unwrap();
 
   assert_eq!(response.data.currency, String::from("USD"));
   assert_eq!(response.data.base, Some(String::from("BTC")));
 }
+
+#[test]
+fn test_debug_redacts_credentials() {
+  let client = Client::private("my-api-key", "my-api-secret");
+  let debug = format!("{:?}", client);
+
+  assert!(!debug.contains("my-api-key"));
+  assert!(!debug.contains("my-api-secret"));
+  assert!(debug.contains("[REDACTED]"));
+}
+
+#[test]
+fn test_transaction_enums() {
+  let kind: TransactionType = serde_json::from_str("\"fiat_deposit\"").unwrap();
+  let status: TransactionStatus = serde_json::from_str("\"waiting_for_clearing\"").unwrap();
+
+  assert!(matches!(kind, TransactionType::FiatDeposit));
+  assert_eq!(status, TransactionStatus::WaitingForClearing);
+  assert_eq!(
+    serde_json::to_string(&TransactionType::ExchangeWithdrawal).unwrap(),
+    "\"exchange_withdrawal\""
+  );
+}
@@ -0,0 +1,129 @@
+#![cfg(unix)]
+
+extern crate coinbase;
+
+use coinbase::config::ENV_BASE_URL;
+use coinbase::config::ENV_KEY;
+use coinbase::config::ENV_LANGUAGE;
+use coinbase::config::ENV_SECRET;
+use coinbase::config::ENV_VERSION;
+use coinbase::prelude::*;
+use std::env::remove_var;
+use std::env::set_var;
+use std::env::temp_dir;
+use std::fs::remove_file;
+use std::fs::set_permissions;
+use std::fs::write;
+use std::fs::Permissions;
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+const TOML: &str = r#"
+[default]
+key = "toml-key"
+secret = "toml-secret"
+
+[sandbox]
+key = "sandbox-key"
+secret = "sandbox-secret"
+language = "es-mx"
+version = "2020-01-01"
+base_url = "http://127.0.0.1:8080/v2"
+"#;
+
+const JSON: &str = r#"{
+  "default": { "language": "de" }
+}"#;
+
+fn profile_file(name: &str, data: &str, mode: u32) -> PathBuf {
+  let path: PathBuf = temp_dir().join(format!("coinbase-{}-{}", std::process::id(), name));
+
+  write(&path, data).unwrap();
+  set_permissions(&path, Permissions::from_mode(mode)).unwrap();
+
+  path
+}
+
+#[test]
+fn test_profile_toml() {
+  let path = profile_file("profile.toml", TOML, 0o600);
+  let client = Client::from_profile_file(&path, "sandbox").unwrap();
+  let debug = format!("{:?}", client);
+
+  remove_file(&path).unwrap();
+
+  assert_eq!(client.language.to_string(), "es-mx");
+  assert_eq!(client.version, "2020-01-01");
+  assert!(debug.contains("http://127.0.0.1:8080/v2/"));
+  assert!(!debug.contains("sandbox-secret"));
+}
+
+#[test]
+fn test_profile_json() {
+  let path = profile_file("profile.json", JSON, 0o600);
+  let client = Client::from_profile_file(&path, "default").unwrap();
+
+  remove_file(&path).unwrap();
+
+  assert_eq!(client.language.to_string(), "de");
+}
+
+#[test]
+fn test_profile_missing() {
+  let path = profile_file("missing.toml", TOML, 0o600);
+  let result = Client::from_profile_file(&path, "production");
+
+  remove_file(&path).unwrap();
+
+  assert!(matches!(result, Err(Error::Config(_))));
+}
+
+#[test]
+fn test_profile_permissions() {
+  let path = profile_file("open.toml", TOML, 0o644);
+  let result = Client::from_profile_file(&path, "default");
+
+  remove_file(&path).unwrap();
+
+  assert!(matches!(result, Err(Error::Config(_))));
+}
+
+// Environment variables are shared by all threads, so every case runs in this
+// one test.
+#[test]
+fn test_from_env() {
+  set_var(ENV_KEY, "env-key");
+  set_var(ENV_SECRET, "env-secret");
+  set_var(ENV_LANGUAGE, "fr");
+  set_var(ENV_VERSION, "2021-01-01");
+  set_var(ENV_BASE_URL, "http://127.0.0.1:9090/v2");
+
+  let client = Client::from_env().unwrap();
+  let debug = format!("{:?}", client);
+
+  assert_eq!(client.language.to_string(), "fr");
+  assert_eq!(client.version, "2021-01-01");
+  assert!(debug.contains("http://127.0.0.1:9090/v2/"));
+  assert!(!debug.contains("env-secret"));
+
+  set_var(ENV_LANGUAGE, "not a language");
+
+  assert!(Client::from_env().is_err());
+
+  remove_var(ENV_LANGUAGE);
+  remove_var(ENV_VERSION);
+  remove_var(ENV_BASE_URL);
+  remove_var(ENV_SECRET);
+
+  match Client::from_env() {
+    Err(Error::Config(message)) => assert!(message.contains(ENV_SECRET)),
+    other => panic!("{:?}", other),
+  }
+
+  remove_var(ENV_KEY);
+
+  match Client::from_env() {
+    Err(Error::Config(message)) => assert!(message.contains(ENV_KEY)),
+    other => panic!("{:?}", other),
+  }
+}
@@ -0,0 +1,294 @@
+extern crate chrono;
+extern crate coinbase;
+
+use chrono::Duration;
+use chrono::NaiveTime;
+use chrono::TimeZone;
+use chrono::Utc;
+use chrono::Weekday;
+use coinbase::dca::Execution;
+use coinbase::dca::ExecutionLog;
+use coinbase::dca::ExecutionStatus;
+use coinbase::dca::JsonLog;
+use coinbase::dca::MemoryLog;
+use coinbase::dca::MissedRuns;
+use coinbase::dca::Plan;
+use coinbase::dca::Schedule;
+use coinbase::dca::Scheduler;
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+use std::env::temp_dir;
+use std::fs::remove_file;
+use std::path::PathBuf;
+
+fn date(y: i32, m: u32, d: u32, h: u32, min: u32) -> UtcDate {
+  Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+}
+
+fn time(h: u32, m: u32) -> NaiveTime {
+  NaiveTime::from_hms_opt(h, m, 0).unwrap()
+}
+
+fn statuses(executions: &[Execution]) -> Vec<ExecutionStatus> {
+  executions.iter().map(|execution| execution.status).collect()
+}
+
+#[test]
+fn test_schedule_daily_weekly() {
+  let daily: Schedule = Schedule::daily(time(9, 30));
+
+  assert_eq!(daily.next_after(date(2024, 3, 4, 8, 0)), Some(date(2024, 3, 4, 9, 30)));
+  assert_eq!(daily.next_after(date(2024, 3, 4, 9, 30)), Some(date(2024, 3, 5, 9, 30)));
+
+  // 2024-03-04 is a Monday.
+  let weekly: Schedule = Schedule::weekly(Weekday::Wed, time(12, 0));
+
+  assert_eq!(weekly.next_after(date(2024, 3, 4, 0, 0)), Some(date(2024, 3, 6, 12, 0)));
+  assert_eq!(
+    weekly.next_after(date(2024, 3, 6, 12, 0)),
+    Some(date(2024, 3, 13, 12, 0))
+  );
+  assert_eq!(
+    weekly.next_after(date(2024, 3, 7, 0, 0)),
+    Some(date(2024, 3, 13, 12, 0))
+  );
+}
+
+#[test]
+fn test_schedule_cron() {
+  let every: Schedule = Schedule::cron("*/15 * * * *").unwrap();
+
+  assert_eq!(every.next_after(date(2024, 3, 4, 8, 7)), Some(date(2024, 3, 4, 8, 15)));
+  assert_eq!(every.next_after(date(2024, 3, 4, 8, 45)), Some(date(2024, 3, 4, 9, 0)));
+
+  let weekdays: Schedule = Schedule::cron("0 9 * * 1-5").unwrap();
+
+  // Friday evening runs again on Monday.
+  assert_eq!(
+    weekdays.next_after(date(2024, 3, 8, 10, 0)),
+    Some(date(2024, 3, 11, 9, 0))
+  );
+
+  let sunday: Schedule = Schedule::cron("30 6 * * 7").unwrap();
+
+  assert_eq!(
+    sunday.next_after(date(2024, 3, 4, 0, 0)),
+    Some(date(2024, 3, 10, 6, 30))
+  );
+
+  let monthly: Schedule = Schedule::cron("0 0 1,15 * *").unwrap();
+
+  assert_eq!(
+    monthly.next_after(date(2024, 2, 15, 0, 0)),
+    Some(date(2024, 3, 1, 0, 0))
+  );
+
+  let leap: Schedule = Schedule::cron("0 0 29 2 *").unwrap();
+
+  assert_eq!(leap.next_after(date(2024, 3, 1, 0, 0)), Some(date(2028, 2, 29, 0, 0)));
+  assert_eq!(
+    Schedule::cron("0 0 31 2 *").unwrap().next_after(date(2024, 1, 1, 0, 0)),
+    None
+  );
+
+  for expression in &[
+    "",
+    "* * * *",
+    "60 * * * *",
+    "* * 0 * *",
+    "5-1 * * * *",
+    "*/0 * * * *",
+    "a * * * *",
+  ] {
+    match Schedule::cron(expression) {
+      Err(Error::Schedule(_)) => {}
+      other => panic!("{:?}: {:?}", expression, other),
+    }
+  }
+}
+
+#[test]
+fn test_scheduler_tick() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let start: UtcDate = date(2024, 3, 4, 0, 0);
+
+  let mut scheduler: Scheduler<MemoryLog> = Scheduler::new(MemoryLog::new())
+    .unwrap()
+    .plan(Plan::new("daily", &btc, "50", "USD", Schedule::daily(time(9, 0))).start(start));
+
+  assert_eq!(scheduler.next_run(start), Some(date(2024, 3, 4, 9, 0)));
+  assert!(scheduler.tick(&client, date(2024, 3, 4, 8, 0)).unwrap().is_empty());
+
+  let executions: Vec<Execution> = scheduler.tick(&client, date(2024, 3, 4, 9, 1)).unwrap();
+
+  assert_eq!(statuses(&executions), vec![ExecutionStatus::Placed]);
+  assert_eq!(executions[0].scheduled, date(2024, 3, 4, 9, 0));
+  assert_eq!(executions[0].total.as_ref().unwrap().currency, "USD");
+  assert_eq!(executions[0].amount.as_ref().unwrap().currency, "BTC");
+
+  let buy: String = executions[0].buy.clone().unwrap();
+
+  assert!(server.state().buys[&btc].iter().any(|item| item.meta.id == buy));
+  assert_eq!(server.state().trade_idems[&executions[0].idem], buy);
+
+  // Already placed.
+  assert!(scheduler.tick(&client, date(2024, 3, 4, 12, 0)).unwrap().is_empty());
+
+  // Pending then placed.
+  assert_eq!(
+    statuses(scheduler.log().executions()),
+    vec![ExecutionStatus::Pending, ExecutionStatus::Placed]
+  );
+}
+
+#[test]
+fn test_scheduler_missed_runs() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let start: UtcDate = date(2024, 3, 4, 0, 0);
+  let now: UtcDate = date(2024, 3, 6, 10, 0);
+  let schedule: Schedule = Schedule::daily(time(9, 0));
+
+  let mut skip: Scheduler<MemoryLog> = Scheduler::new(MemoryLog::new())
+    .unwrap()
+    .plan(Plan::new("skip", &btc, "10", "USD", schedule.clone()).start(start));
+
+  let executions: Vec<Execution> = skip.tick(&client, now).unwrap();
+
+  assert_eq!(
+    statuses(&executions),
+    vec![
+      ExecutionStatus::Skipped,
+      ExecutionStatus::Skipped,
+      ExecutionStatus::Placed
+    ]
+  );
+  assert_eq!(executions[2].scheduled, date(2024, 3, 6, 9, 0));
+  assert_eq!(server.state().buys[&btc].len(), 1);
+
+  let mut catch_up: Scheduler<MemoryLog> = Scheduler::new(MemoryLog::new()).unwrap().plan(
+    Plan::new("catch-up", &btc, "10", "USD", schedule)
+      .start(start)
+      .missed(MissedRuns::CatchUp),
+  );
+
+  let executions: Vec<Execution> = catch_up.tick(&client, now).unwrap();
+
+  assert_eq!(statuses(&executions), vec![ExecutionStatus::Placed; 3]);
+  assert_eq!(server.state().buys[&btc].len(), 4);
+}
+
+#[test]
+fn test_scheduler_dry_run() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+
+  let mut scheduler: Scheduler<MemoryLog> = Scheduler::new(MemoryLog::new())
+    .unwrap()
+    .dry_run(true)
+    .plan(Plan::new("dry", &btc, "10", "USD", Schedule::daily(time(9, 0))).start(date(2024, 3, 4, 0, 0)));
+
+  let executions: Vec<Execution> = scheduler.tick(&client, date(2024, 3, 4, 10, 0)).unwrap();
+
+  assert_eq!(statuses(&executions), vec![ExecutionStatus::DryRun]);
+  assert!(executions[0].buy.is_none());
+  assert!(server.state().buys.get(&btc).is_none_or(Vec::is_empty));
+}
+
+#[test]
+fn test_scheduler_failed() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+
+  let mut scheduler: Scheduler<MemoryLog> = Scheduler::new(MemoryLog::new()).unwrap().plan(
+    Plan::new("invalid", &btc, "10", "USD", Schedule::daily(time(9, 0)))
+      .payment_method("missing")
+      .start(date(2024, 3, 4, 0, 0)),
+  );
+
+  let executions: Vec<Execution> = scheduler.tick(&client, date(2024, 3, 4, 10, 0)).unwrap();
+
+  assert_eq!(statuses(&executions), vec![ExecutionStatus::Failed]);
+  assert!(executions[0].error.as_ref().unwrap().contains("Invalid payment method"));
+
+  // Failed runs are not retried.
+  assert!(scheduler.tick(&client, date(2024, 3, 4, 11, 0)).unwrap().is_empty());
+}
+
+#[test]
+fn test_scheduler_retry_pending() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let plan: Plan = Plan::new("retry", &btc, "10", "USD", Schedule::daily(time(9, 0))).start(date(2024, 3, 4, 0, 0));
+  let scheduled: UtcDate = date(2024, 3, 4, 9, 0);
+
+  // The buy went through, but the process died before recording it.
+  client
+    .create_buy(&btc, BuyRequest::new("10", "USD").with_idem(&plan.idem(scheduled)))
+    .unwrap();
+
+  let mut log: MemoryLog = MemoryLog::new();
+
+  log
+    .append(&Execution {
+      plan: plan.id.clone(),
+      scheduled,
+      recorded: scheduled,
+      status: ExecutionStatus::Pending,
+      idem: plan.idem(scheduled),
+      buy: None,
+      amount: None,
+      total: None,
+      error: None,
+    })
+    .unwrap();
+
+  let mut scheduler: Scheduler<MemoryLog> = Scheduler::new(log).unwrap().plan(plan);
+  let executions: Vec<Execution> = scheduler.tick(&client, date(2024, 3, 4, 10, 0)).unwrap();
+
+  assert_eq!(statuses(&executions), vec![ExecutionStatus::Placed]);
+  assert_eq!(executions[0].scheduled, scheduled);
+  assert_eq!(server.state().buys[&btc].len(), 1);
+  assert_eq!(executions[0].buy.as_ref(), Some(&server.state().buys[&btc][0].meta.id));
+}
+
+#[test]
+fn test_json_log() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let path: PathBuf = temp_dir().join(format!("coinbase-dca-{}.jsonl", std::process::id()));
+  let plan: Plan = Plan::new("json", &btc, "10", "USD", Schedule::daily(time(9, 0))).start(date(2024, 3, 4, 0, 0));
+
+  let _ = remove_file(&path);
+
+  let mut scheduler: Scheduler<JsonLog> = Scheduler::new(JsonLog::new(&path)).unwrap().plan(plan.clone());
+
+  scheduler.tick(&client, date(2024, 3, 4, 10, 0)).unwrap();
+
+  let loaded: Vec<Execution> = JsonLog::new(&path).load().unwrap();
+
+  assert_eq!(
+    statuses(&loaded),
+    vec![ExecutionStatus::Pending, ExecutionStatus::Placed]
+  );
+  assert_eq!(loaded[1].buy, scheduler.executions()[1].buy);
+
+  // A restarted scheduler resumes after the last run.
+  let mut scheduler: Scheduler<JsonLog> = Scheduler::new(JsonLog::new(&path)).unwrap().plan(plan);
+  let next: UtcDate = date(2024, 3, 4, 10, 0) + Duration::days(1);
+
+  assert_eq!(
+    statuses(&scheduler.tick(&client, next).unwrap()),
+    vec![ExecutionStatus::Placed]
+  );
+  assert_eq!(JsonLog::new(&path).load().unwrap().len(), 4);
+
+  remove_file(&path).unwrap();
+}
@@ -0,0 +1,195 @@
+extern crate chrono;
+extern crate coinbase;
+
+use chrono::Duration;
+use chrono::Utc;
+use coinbase::export::write_ofx;
+use coinbase::export::write_qif;
+use coinbase::export::Activity;
+use coinbase::export::Column;
+use coinbase::export::Export;
+use coinbase::export::Statement;
+use coinbase::export::StatementKind;
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+use serde_json::Value;
+use std::collections::HashMap;
+
+#[test]
+fn test_activity_from_transaction() {
+  let mut network: ValueMap = ValueMap::new();
+  let mut fee: ValueMap = ValueMap::new();
+
+  fee.insert("amount".into(), Value::String("0.0001".into()));
+  fee.insert("currency".into(), Value::String("BTC".into()));
+  network.insert("hash".into(), Value::String("abc123".into()));
+  network.insert("transaction_fee".into(), Value::Object(fee.into_iter().collect()));
+
+  let transaction: Transaction = Transaction {
+    kind: TransactionType::Send,
+    status: TransactionStatus::Completed,
+    amount: Money {
+      amount: -0.5,
+      currency: "BTC".into(),
+      ..Default::default()
+    },
+    network: Some(network),
+    ..Default::default()
+  };
+
+  let activity: Activity = Activity::from_transaction("account", &transaction);
+
+  assert_eq!(activity.kind, "send");
+  assert_eq!(activity.status, "completed");
+  assert_eq!(activity.amount.to_string(), "-0.5");
+  assert_eq!(activity.fee.unwrap().to_string(), "0.0001");
+  assert_eq!(activity.network_hash.as_deref(), Some("abc123"));
+  assert!(matches!(activity.transaction_type(), Some(TransactionType::Send)));
+}
+
+#[test]
+fn test_export_csv() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+
+  let buy: HashMap<&str, &str> = vec![("amount", "0.5"), ("currency", "BTC")].into_iter().collect();
+  let send: HashMap<&str, &str> = vec![
+    ("type", "send"),
+    ("to", "a@b.c"),
+    ("amount", "0.1"),
+    ("currency", "BTC"),
+  ]
+  .into_iter()
+  .collect();
+
+  client.create_buy(&btc, &buy).unwrap();
+  client.create_transaction(&btc, &send).unwrap();
+
+  let mut output: Vec<u8> = Vec::new();
+  let rows: usize = client.export_csv(&Export::new(), &mut output).unwrap();
+  let output: String = String::from_utf8(output).unwrap();
+  let lines: Vec<&str> = output.lines().collect();
+
+  assert_eq!(rows, 2);
+  assert_eq!(lines.len(), 3);
+  assert_eq!(
+    lines[0],
+    "timestamp,type,status,amount,currency,native_amount,native_currency,fee,fee_currency,counterparty,network_hash,resource_id"
+  );
+
+  assert!(lines
+    .iter()
+    .any(|line| line.contains(",buy,completed,0.5,BTC,5125.75,USD,75.75,USD,")));
+  assert!(lines
+    .iter()
+    .any(|line| line.contains(",send,pending,-0.1,BTC,-1000,USD,,,a@b.c,,")));
+
+  let export: Export =
+    Export::new()
+      .resources(&[ResourceType::Transaction])
+      .columns(&[Column::Type, Column::Amount, Column::Account]);
+
+  let mut output: Vec<u8> = Vec::new();
+
+  client.export_csv(&export, &mut output).unwrap();
+
+  let output: String = String::from_utf8(output).unwrap();
+
+  assert!(output.starts_with("type,amount,account\n"));
+  assert!(output.contains(&format!("send,-0.1,{}\n", btc)));
+  assert_eq!(output.lines().count(), 3);
+
+  let future: Export = Export::new().from(Utc::now() + Duration::hours(1));
+
+  assert_eq!(client.activity(&future).unwrap().len(), 0);
+  assert_eq!(client.activity(&Export::new().to(Utc::now())).unwrap().len(), 2);
+}
+
+fn statements(server: &MockServer) -> Vec<Statement> {
+  let client = server.client();
+  let usd: String = server.state().accounts[0].meta.id.clone();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let vault: String = server.state().add_account("BTC Vault", "BTC", 0.0).meta.id;
+
+  let buy: HashMap<&str, &str> = vec![("amount", "0.5"), ("currency", "BTC")].into_iter().collect();
+  let sell: HashMap<&str, &str> = vec![("amount", "0.2"), ("currency", "BTC")].into_iter().collect();
+  let transfer: HashMap<&str, &str> = vec![
+    ("type", "transfer"),
+    ("to", &vault),
+    ("amount", "0.1"),
+    ("currency", "BTC"),
+  ]
+  .into_iter()
+  .collect();
+
+  client.create_buy(&btc, &buy).unwrap();
+  client.create_sell(&btc, &sell).unwrap();
+  client.create_transaction(&btc, &transfer).unwrap();
+
+  server
+    .state()
+    .add_transaction(&usd, TransactionType::FiatDeposit, TransactionStatus::Completed, 250.0)
+    .unwrap();
+
+  client.statements().unwrap()
+}
+
+#[test]
+fn test_statement_kinds() {
+  let server = MockServer::start().unwrap();
+  let statements: Vec<Statement> = statements(&server);
+
+  let kinds = |index: usize| -> Vec<StatementKind> { statements[index].entries().map(|(_, kind)| kind).collect() };
+
+  assert_eq!(kinds(0), vec![StatementKind::Credit]);
+  assert_eq!(
+    kinds(1),
+    vec![StatementKind::Buy, StatementKind::Sell, StatementKind::TransferOut]
+  );
+  assert_eq!(kinds(3), vec![StatementKind::TransferIn]);
+}
+
+#[test]
+fn test_export_ofx() {
+  let server = MockServer::start().unwrap();
+  let statements: Vec<Statement> = statements(&server);
+  let mut output: Vec<u8> = Vec::new();
+
+  assert_eq!(write_ofx(&statements, &mut output).unwrap(), 5);
+
+  let output: String = String::from_utf8(output).unwrap();
+
+  assert!(output.contains(r#"<?OFX OFXHEADER="200" VERSION="220""#));
+  assert_eq!(output.matches("<INVSTMTTRNRS>").count(), 4);
+  assert!(output.contains("<CURDEF>USD</CURDEF>"));
+  assert!(output.contains("<BUYOTHER><INVBUY>"));
+  assert!(output.contains("<UNITS>0.5</UNITS><UNITPRICE>10251.5</UNITPRICE><TOTAL>-5125.75</TOTAL>"));
+  assert!(output.contains("<SELLOTHER><INVSELL>"));
+  assert!(output.contains("<UNITS>-0.2</UNITS>"));
+  assert!(output.contains("<UNITS>-0.1</UNITS><TFERACTION>OUT</TFERACTION>"));
+  assert!(output.contains("<UNITS>0.1</UNITS><TFERACTION>IN</TFERACTION>"));
+  assert!(output.contains("<TRNTYPE>CREDIT</TRNTYPE>"));
+  assert!(output.contains("<TRNAMT>250</TRNAMT>"));
+  assert!(output.contains("<SECNAME>BTC</SECNAME><TICKER>BTC</TICKER>"));
+  assert!(output.trim_end().ends_with("</OFX>"));
+}
+
+#[test]
+fn test_export_qif() {
+  let server = MockServer::start().unwrap();
+  let statements: Vec<Statement> = statements(&server);
+  let mut output: Vec<u8> = Vec::new();
+
+  assert_eq!(write_qif(&statements, &mut output).unwrap(), 5);
+
+  let output: String = String::from_utf8(output).unwrap();
+
+  assert!(output.starts_with("!Account\nNUSD Wallet\nTBank\n^\n!Type:Bank\n"));
+  assert!(output.contains("T250\nN"));
+  assert!(output.contains("!Account\nNBTC Wallet\nTInvst\n^\n!Type:Invst\n"));
+  assert!(output.contains("NBuy\nYBTC\nI10251.5\nQ0.5\nT5125.75\nMbuy\n^\n"));
+  assert!(output.contains("NSell\nYBTC\n"));
+  assert!(output.contains("NShrsOut\nYBTC\n"));
+  assert!(output.contains("NShrsIn\nYBTC\n"));
+}
@@ -0,0 +1,137 @@
+extern crate chrono;
+extern crate coinbase;
+
+use chrono::NaiveDate;
+use chrono::TimeZone;
+use chrono::Utc;
+use coinbase::history::BalanceHistory;
+use coinbase::history::DailyBalance;
+use coinbase::history::Discrepancy;
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+
+fn account(balance: f64) -> Account {
+  Account {
+    meta: ResourceMeta {
+      id: "account".into(),
+      ..Default::default()
+    },
+    balance: Money {
+      amount: balance,
+      currency: "BTC".into(),
+      ..Default::default()
+    },
+    ..Default::default()
+  }
+}
+
+fn transaction(id: &str, day: u32, status: TransactionStatus, amount: f64) -> Transaction {
+  Transaction {
+    meta: ResourceMeta {
+      id: id.into(),
+      created_at: Some(Utc.with_ymd_and_hms(2020, 1, day, 12, 0, 0).unwrap().into()),
+      ..Default::default()
+    },
+    kind: TransactionType::Send,
+    status,
+    amount: Money {
+      amount,
+      currency: "BTC".into(),
+      ..Default::default()
+    },
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_replay() {
+  let transactions: Vec<Transaction> = vec![
+    transaction("c", 4, TransactionStatus::Completed, -0.25),
+    transaction("a", 1, TransactionStatus::Completed, 1.0),
+    transaction("p", 2, TransactionStatus::Pending, 5.0),
+    transaction("b", 2, TransactionStatus::Completed, 0.5),
+  ];
+
+  let history: BalanceHistory = BalanceHistory::new(&account(1.25), &transactions);
+  let balances: Vec<String> = history.points.iter().map(|point| point.balance.to_string()).collect();
+
+  assert!(history.is_consistent());
+  assert_eq!(history.opening.to_string(), "0");
+  assert_eq!(balances, vec!["1", "1.5", "1.25"]);
+  assert_eq!(
+    history
+      .balance_at(Utc.with_ymd_and_hms(2020, 1, 3, 0, 0, 0).unwrap())
+      .to_string(),
+    "1.5"
+  );
+
+  let daily: Vec<DailyBalance> = history.daily();
+
+  assert_eq!(daily.len(), 4);
+  assert_eq!(daily[2].date, NaiveDate::from_ymd_opt(2020, 1, 3).unwrap());
+  assert_eq!(daily[2].balance.to_string(), "1.5");
+  assert_eq!(daily[2].transactions, 0);
+  assert_eq!(daily[3].change.to_string(), "-0.25");
+}
+
+#[test]
+fn test_discrepancies() {
+  let mut other: Transaction = transaction("e", 3, TransactionStatus::Completed, 10.0);
+
+  other.amount.currency = "ETH".into();
+
+  let transactions: Vec<Transaction> = vec![
+    transaction("a", 1, TransactionStatus::Completed, -1.0),
+    transaction("b", 2, TransactionStatus::Completed, 0.5),
+    other,
+  ];
+
+  let history: BalanceHistory = BalanceHistory::new(&account(0.0), &transactions);
+
+  assert!(!history.is_consistent());
+  assert_eq!(history.opening.to_string(), "0.5");
+  assert_eq!(
+    history.discrepancies,
+    vec![
+      Discrepancy::Currency {
+        transaction: "e".into(),
+        currency: "ETH".into(),
+      },
+      Discrepancy::Unexplained(history.opening),
+      Discrepancy::Negative {
+        transaction: "a".into(),
+        balance: history.points[0].balance,
+      },
+    ]
+  );
+}
+
+#[test]
+fn test_balance_history() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let wallet: String = server.state().add_account("BTC Vault", "BTC", 0.0).meta.id;
+  let funded: String = server.state().add_account("ETH Vault", "ETH", 2.0).meta.id;
+
+  for (status, amount) in [
+    (TransactionStatus::Completed, 1.5),
+    (TransactionStatus::Pending, 3.0),
+    (TransactionStatus::Completed, -0.5),
+  ] {
+    server
+      .state()
+      .add_transaction(&wallet, TransactionType::Send, status, amount)
+      .unwrap();
+  }
+
+  let history: BalanceHistory = client.balance_history(&wallet).unwrap();
+
+  assert!(history.is_consistent());
+  assert_eq!(history.balance.to_string(), "1");
+  assert_eq!(history.points.len(), 2);
+  assert_eq!(history.daily().len(), 1);
+
+  let history: BalanceHistory = client.balance_history(&funded).unwrap();
+
+  assert_eq!(history.discrepancies, vec![Discrepancy::Unexplained(history.balance)]);
+}
@@ -0,0 +1,102 @@
+extern crate coinbase;
+
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+use coinbase::transport::*;
+use std::io::Error as IO;
+use std::io::ErrorKind;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+// Delivers the first request but loses its response.
+#[derive(Debug)]
+struct LostResponse {
+  lost: AtomicBool,
+  inner: HttpTransport,
+}
+
+impl Transport for LostResponse {
+  fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+    let response: HttpResponse = self.inner.send(request)?;
+
+    if !self.lost.swap(true, Ordering::SeqCst) {
+      return Err(Error::IO(IO::new(ErrorKind::ConnectionReset, "connection reset")));
+    }
+
+    Ok(response)
+  }
+}
+
+#[test]
+fn test_send_generates_idem() {
+  let first = SendMoney::new("satoshi@example.com", "0.1", "BTC");
+  let second = SendMoney::new("satoshi@example.com", "0.1", "BTC");
+
+  assert_eq!(first.idem().len(), 36);
+  assert_ne!(first.idem(), second.idem());
+  assert_eq!(first.clone().with_idem("saved").idem(), "saved");
+}
+
+#[test]
+fn test_send_replay() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let account = client.list_accounts().unwrap().data[1].meta.id.clone();
+
+  let request = SendMoney::new("satoshi@example.com", "0.1", "BTC").description("Lunch");
+  let sent = client.send_money(&account, &request).unwrap().data;
+
+  assert_eq!(sent.idem.as_deref(), Some(request.idem()));
+  assert_eq!(sent.description.as_deref(), Some("Lunch"));
+
+  let replay = SendMoney::new("satoshi@example.com", "0.1", "BTC").with_idem(request.idem());
+  let replayed = client.send_money(&account, &replay).unwrap().data;
+
+  assert_eq!(replayed.meta.id, sent.meta.id);
+  assert_eq!(client.list_transactions(&account).unwrap().data.len(), 1);
+}
+
+#[test]
+fn test_send_at_most_once_after_network_failure() {
+  let server = MockServer::start().unwrap();
+
+  let transport = LostResponse {
+    lost: AtomicBool::new(false),
+    inner: HttpTransport::default(),
+  };
+
+  let client = server
+    .builder()
+    .transport(transport)
+    .retry_policy(RetryPolicy {
+      base_delay: Duration::from_millis(0),
+      ..Default::default()
+    })
+    .build()
+    .unwrap();
+
+  let account = server.client().list_accounts().unwrap().data[1].meta.id.clone();
+  let request = SendMoney::new("satoshi@example.com", "0.1", "BTC");
+
+  client.send_money(&account, &request).unwrap();
+
+  assert_eq!(server.client().list_transactions(&account).unwrap().data.len(), 1);
+}
+
+#[test]
+fn test_transfer() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc = client.list_accounts().unwrap().data[1].meta.id.clone();
+  let vault = server.state().add_account("BTC Vault", "BTC", 0.0).meta.id;
+
+  let transfer = client
+    .transfer_money(&btc, &TransferMoney::new(&vault, "0.4", "BTC"))
+    .unwrap()
+    .data;
+
+  assert!(matches!(transfer.kind, TransactionType::Transfer));
+  assert_eq!(client.get_account(&btc).unwrap().data.balance.amount, 0.6);
+  assert_eq!(client.get_account(&vault).unwrap().data.balance.amount, 0.4);
+}
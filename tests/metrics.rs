@@ -0,0 +1,97 @@
+extern crate coinbase;
+
+use coinbase::metrics::Histogram;
+use coinbase::metrics::InMemoryMetrics;
+use coinbase::prelude::*;
+use coinbase::transport::*;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SPOT: &str = r#"{"data":{"base":"BTC","currency":"USD","amount":"7225.51"}}"#;
+const UNAVAILABLE: &str = r#"{"errors":[{"id":"internal_server_error","message":"Unavailable"}]}"#;
+const NOT_FOUND: &str = r#"{"errors":[{"id":"not_found","message":"Not found"}]}"#;
+
+fn limited() -> HttpResponse {
+  let mut response: HttpResponse = HttpResponse::json(429, "{}");
+
+  response.headers.insert("Retry-After", "0".parse().unwrap());
+  response
+}
+
+fn spot() -> HttpResponse {
+  let mut response: HttpResponse = HttpResponse::json(200, SPOT);
+
+  response.headers.insert("CB-RateLimit-Limit", "10000".parse().unwrap());
+  response
+    .headers
+    .insert("CB-RateLimit-Remaining", "9998".parse().unwrap());
+  response
+}
+
+#[test]
+fn test_histogram() {
+  let mut histogram: Histogram = Histogram::default();
+
+  histogram.observe(Duration::from_millis(5));
+  histogram.observe(Duration::from_millis(30));
+  histogram.observe(Duration::from_millis(30));
+  histogram.observe(Duration::from_secs(60));
+
+  assert_eq!(histogram.count, 4);
+  assert_eq!(histogram.below(10), 1);
+  assert_eq!(histogram.below(50), 3);
+  assert_eq!(histogram.below(10000), 3);
+  assert_eq!(histogram.buckets[10], 1);
+}
+
+#[test]
+fn test_metrics() {
+  let mock = MockTransport::new();
+  let metrics = Arc::new(InMemoryMetrics::new());
+
+  mock
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(503, UNAVAILABLE))
+    .mock(Method::GET, "prices/BTC-USD/spot", limited())
+    .mock(Method::GET, "prices/BTC-USD/spot", spot());
+
+  mock.mock(Method::GET, "accounts/missing", HttpResponse::json(404, NOT_FOUND));
+  mock.mock(Method::GET, "accounts/broken", HttpResponse::json(500, ""));
+
+  let client = Client::builder()
+    .transport(mock)
+    .retry_policy(RetryPolicy {
+      base_delay: Duration::from_millis(0),
+      ..Default::default()
+    })
+    .metrics(metrics.clone())
+    .build()
+    .unwrap();
+
+  client.spot_price("BTC", "USD").unwrap();
+  client.get_account("missing").unwrap();
+  assert!(client.get_account("broken").is_err());
+  assert!(client.time().is_err());
+
+  let spot = metrics.endpoint("prices/{pair}/spot");
+
+  assert_eq!(spot.requests, 3);
+  assert_eq!(spot.retries, 1);
+  assert_eq!(spot.throttled, 1);
+  assert_eq!(spot.errors["internal_server_error"], 1);
+  assert_eq!(spot.errors["status_429"], 1);
+  assert_eq!(spot.latency.count, 3);
+
+  let account = metrics.endpoint("accounts/{id}");
+
+  assert_eq!(account.requests, 4);
+  assert_eq!(account.errors["not_found"], 1);
+  assert_eq!(account.errors["status_500"], 3);
+  assert_eq!(account.retries, 2);
+
+  assert_eq!(metrics.endpoint("time").errors["transport"], 1);
+  assert_eq!(metrics.headroom().unwrap().remaining, Some(9998));
+
+  metrics.reset();
+
+  assert!(metrics.endpoints().is_empty());
+}
@@ -0,0 +1,78 @@
+extern crate coinbase;
+
+use coinbase::prelude::*;
+use coinbase::transport::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const SPOT: &str = r#"{"data":{"base":"BTC","currency":"USD","amount":"7225.51"}}"#;
+
+#[derive(Debug)]
+struct CorrelationId;
+
+impl Middleware for CorrelationId {
+  fn before_send(&self, request: &mut HttpRequest) {
+    request.headers.insert("X-Correlation-Id", "abc-123".parse().unwrap());
+  }
+}
+
+#[derive(Debug, Default)]
+struct Counter {
+  calls: Mutex<HashMap<String, usize>>,
+  statuses: Mutex<Vec<u16>>,
+  errors: Mutex<usize>,
+  latency: Mutex<Vec<Duration>>,
+}
+
+impl Middleware for Counter {
+  fn before_send(&self, request: &mut HttpRequest) {
+    assert!(request.headers.contains_key("X-Correlation-Id"));
+    assert!(request.headers.contains_key("CB-ACCESS-SIGN"));
+
+    *self
+      .calls
+      .lock()
+      .unwrap()
+      .entry(request.url.path().to_owned())
+      .or_default() += 1;
+  }
+
+  fn after_receive(&self, _request: &HttpRequest, response: &HttpResponse, latency: Duration) {
+    self.statuses.lock().unwrap().push(response.status.as_u16());
+    self.latency.lock().unwrap().push(latency);
+  }
+
+  fn on_error(&self, _request: &HttpRequest, _error: &Error, _latency: Duration) {
+    *self.errors.lock().unwrap() += 1;
+  }
+}
+
+#[test]
+fn test_middleware_chain() {
+  let mock = Arc::new(MockTransport::new());
+  let counter = Arc::new(Counter::default());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  let client = Client::builder()
+    .credentials("key", "secret")
+    .transport(mock.clone())
+    .middleware(CorrelationId)
+    .middleware(counter.clone())
+    .build()
+    .unwrap();
+
+  client.spot_price("BTC", "USD").unwrap();
+  client.spot_price("BTC", "USD").unwrap();
+
+  assert!(client.time().is_err());
+
+  assert_eq!(mock.requests()[0].headers["X-Correlation-Id"], "abc-123");
+  assert_eq!(counter.calls.lock().unwrap()["/v2/prices/BTC-USD/spot"], 2);
+  assert_eq!(counter.calls.lock().unwrap()["/v2/time"], 1);
+  assert_eq!(*counter.statuses.lock().unwrap(), vec![200, 200]);
+  assert_eq!(counter.latency.lock().unwrap().len(), 2);
+  assert_eq!(*counter.errors.lock().unwrap(), 1);
+}
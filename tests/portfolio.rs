@@ -0,0 +1,60 @@
+extern crate coinbase;
+extern crate rust_decimal;
+
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+fn dec(value: &str) -> Decimal {
+  Decimal::from_str(value).unwrap()
+}
+
+#[test]
+fn test_all_accounts_paginates() {
+  let server = MockServer::start().unwrap();
+
+  for index in 0..150 {
+    server.state().add_account(&format!("Wallet {}", index), "USD", 0.0);
+  }
+
+  let accounts = server.client().all_accounts().unwrap();
+
+  assert_eq!(accounts.len(), 153);
+  assert_eq!(accounts[0].name, "USD Wallet");
+  assert_eq!(accounts[152].name, "Wallet 149");
+  assert!(accounts.iter().any(|account| account.name == "BTC Wallet"));
+}
+
+#[test]
+fn test_portfolio() {
+  let server = MockServer::start().unwrap();
+
+  server.state().add_account("Savings", "USD", 0.1);
+  server.state().add_account("Dust", "XRP", 5.0);
+
+  let portfolio: Portfolio = server.client().portfolio().unwrap();
+
+  assert_eq!(portfolio.currency, "USD");
+  assert_eq!(portfolio.holdings.len(), 5);
+  assert_eq!(portfolio.total, dec("13000.1"));
+
+  let holding = |name: &str| portfolio.holdings.iter().find(|holding| holding.name == name).unwrap();
+  let btc = holding("BTC Wallet");
+
+  assert_eq!(btc.currency, "BTC");
+  assert_eq!(btc.balance, dec("1"));
+  assert_eq!(btc.native_value, Some(dec("10000")));
+  assert_eq!(btc.share.round_dp(4), dec("0.7692"));
+
+  assert_eq!(holding("Savings").native_value, Some(dec("0.1")));
+
+  let unpriced: Vec<&str> = portfolio.unpriced().map(|holding| holding.currency.as_str()).collect();
+
+  assert_eq!(unpriced, vec!["XRP"]);
+  assert_eq!(holding("Dust").share, Decimal::ZERO);
+
+  let shares: Decimal = portfolio.holdings.iter().map(|holding| holding.share).sum();
+
+  assert_eq!(shares.round_dp(20), Decimal::ONE);
+}
@@ -0,0 +1,102 @@
+extern crate chrono;
+extern crate coinbase;
+
+use chrono::NaiveDate;
+use coinbase::cache::Cache;
+use coinbase::metrics::InMemoryMetrics;
+use coinbase::prelude::*;
+use coinbase::prices::SpotPrices;
+use coinbase::server::MockServer;
+use std::sync::Arc;
+
+const PAIRS: &[(&str, &str)] = &[
+  ("BTC", "USD"),
+  ("btc", "usd"),
+  ("ETH", "USD"),
+  ("XXX", "USD"),
+  ("BTC", "EUR"),
+];
+
+fn pair(base: &str, quote: &str) -> (String, String) {
+  (base.to_owned(), quote.to_owned())
+}
+
+#[test]
+fn test_spot_prices() {
+  let server = MockServer::start().unwrap();
+  let metrics = Arc::new(InMemoryMetrics::new());
+  let client = server.builder().metrics(metrics.clone()).build().unwrap();
+
+  server.state().set_price("BTC", "EUR", 9_000.0);
+
+  let prices = client.spot_prices_with(PAIRS, &SpotPrices::new().concurrency(2));
+
+  assert_eq!(prices.len(), 4);
+  assert_eq!(prices[&pair("BTC", "USD")].as_ref().unwrap().amount, 10_000.0);
+  assert_eq!(prices[&pair("ETH", "USD")].as_ref().unwrap().amount, 200.0);
+  assert_eq!(prices[&pair("BTC", "EUR")].as_ref().unwrap().amount, 9_000.0);
+
+  match prices[&pair("XXX", "USD")] {
+    Err(Error::API(ref errors)) => assert_eq!(errors[0].id, "not_found"),
+    ref other => panic!("unexpected result: {:?}", other),
+  }
+
+  assert_eq!(metrics.endpoint("prices/{pair}/spot").requests, 4);
+}
+
+#[test]
+fn test_spot_prices_from_rates() {
+  let server = MockServer::start().unwrap();
+  let metrics = Arc::new(InMemoryMetrics::new());
+  let client = server.builder().metrics(metrics.clone()).build().unwrap();
+
+  server.state().set_price("BTC", "EUR", 9_000.0);
+
+  let prices = client.spot_prices_with(PAIRS, &SpotPrices::new().from_rates(true));
+
+  assert_eq!(prices.len(), 4);
+  assert_eq!(prices[&pair("BTC", "USD")].as_ref().unwrap().amount, 10_000.0);
+  assert_eq!(prices[&pair("ETH", "USD")].as_ref().unwrap().amount, 200.0);
+  assert_eq!(prices[&pair("BTC", "EUR")].as_ref().unwrap().amount, 9_000.0);
+  assert!(prices[&pair("XXX", "USD")].is_err());
+
+  assert_eq!(metrics.endpoint("exchange-rates").requests, 1);
+  assert_eq!(metrics.endpoint("prices/{pair}/spot").requests, 2);
+}
+
+#[test]
+fn test_spot_price_history() {
+  let server = MockServer::start().unwrap();
+  let metrics = Arc::new(InMemoryMetrics::new());
+  let client = server
+    .builder()
+    .metrics(metrics.clone())
+    .cache(Cache::new())
+    .build()
+    .unwrap();
+
+  let date = |day: u32| NaiveDate::from_ymd_opt(2019, 1, day).unwrap();
+
+  server.state().set_historic_price("BTC", "USD", "2019-01-01", 3_800.0);
+  server.state().set_historic_price("BTC", "USD", "2019-01-02", 3_900.0);
+  server.state().set_historic_price("BTC", "USD", "2019-01-03", 3_850.0);
+
+  let series = client.spot_price_history("btc", "usd", date(1), date(3)).unwrap();
+
+  assert_eq!(series.base, "BTC");
+  assert_eq!(series.quote, "USD");
+  assert_eq!(series.len(), 3);
+  assert_eq!(series.first().unwrap().date, date(1));
+  assert_eq!(series.get(date(2)), Some(3_900.0));
+  assert_eq!(series.last().unwrap().price, 3_850.0);
+  assert_eq!(series.get(date(4)), None);
+
+  client.spot_price_history("BTC", "USD", date(2), date(3)).unwrap();
+
+  assert_eq!(metrics.endpoint("prices/{pair}/spot").requests, 3);
+  assert!(client
+    .spot_price_history("BTC", "USD", date(3), date(1))
+    .unwrap()
+    .is_empty());
+  assert!(client.spot_price_history("XXX", "USD", date(1), date(3)).is_err());
+}
@@ -0,0 +1,141 @@
+extern crate coinbase;
+
+use coinbase::prelude::*;
+use coinbase::ratelimit::RateLimitStatus;
+use coinbase::ratelimit::RateLimiter;
+use coinbase::transport::*;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+const SPOT: &str = r#"{"data":{"base":"BTC","currency":"USD","amount":"7225.51"}}"#;
+const LIMITED: &str = r#"{"errors":[{"id":"rate_limit_exceeded","message":"Too many requests"}]}"#;
+
+fn limited() -> HttpResponse {
+  let mut response = HttpResponse::json(429, LIMITED);
+
+  response.headers.insert("Retry-After", "0".parse().unwrap());
+  response
+}
+
+#[test]
+fn test_retry_after_rate_limit() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", limited()).mock(
+    Method::GET,
+    "prices/BTC-USD/spot",
+    HttpResponse::json(200, SPOT),
+  );
+
+  let client = Client::builder().transport(mock.clone()).build().unwrap();
+
+  assert_eq!(client.spot_price("BTC", "USD").unwrap().data.amount, 7225.51);
+  assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn test_retry_after_date() {
+  let mock = Arc::new(MockTransport::new());
+  let mut response = HttpResponse::json(429, LIMITED);
+
+  response
+    .headers
+    .insert("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", response).mock(
+    Method::GET,
+    "prices/BTC-USD/spot",
+    HttpResponse::json(200, SPOT),
+  );
+
+  let client = Client::builder().transport(mock.clone()).build().unwrap();
+  let start: Instant = Instant::now();
+
+  // A date in the past retries immediately instead of backing off.
+  assert_eq!(client.spot_price("BTC", "USD").unwrap().data.amount, 7225.51);
+  assert!(start.elapsed() < Duration::from_secs(1));
+  assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn test_max_retry_after() {
+  let mock = Arc::new(MockTransport::new());
+  let mut response = HttpResponse::json(429, LIMITED);
+
+  response.headers.insert("Retry-After", "3600".parse().unwrap());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", response).mock(
+    Method::GET,
+    "prices/BTC-USD/spot",
+    HttpResponse::json(200, SPOT),
+  );
+
+  let client = Client::builder().transport(mock.clone()).build().unwrap();
+  let start: Instant = Instant::now();
+
+  assert_eq!(
+    client.spot_price("BTC", "USD").unwrap().errors[0].id,
+    "rate_limit_exceeded"
+  );
+  assert!(start.elapsed() < Duration::from_secs(1));
+  assert_eq!(mock.requests().len(), 1);
+}
+
+#[test]
+fn test_retries_exhausted() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", limited());
+
+  let client = Client::builder()
+    .transport(mock.clone())
+    .rate_limit_retries(2)
+    .build()
+    .unwrap();
+
+  let response = client.spot_price("BTC", "USD").unwrap();
+
+  assert_eq!(response.errors[0].id, "rate_limit_exceeded");
+  assert_eq!(mock.requests().len(), 3);
+}
+
+#[test]
+fn test_rate_limit_headers() {
+  let mock = MockTransport::new();
+  let mut response = HttpResponse::json(200, SPOT);
+
+  response.headers.insert("CB-RateLimit-Limit", "10000".parse().unwrap());
+  response
+    .headers
+    .insert("CB-RateLimit-Remaining", "9998".parse().unwrap());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", response);
+
+  let client = Client::builder().transport(mock).build().unwrap();
+
+  assert_eq!(client.rate_limit(), None);
+
+  client.spot_price("BTC", "USD").unwrap();
+
+  assert_eq!(
+    client.rate_limit(),
+    Some(RateLimitStatus {
+      limit: Some(10000),
+      remaining: Some(9998),
+      reset: None,
+    })
+  );
+}
+
+#[test]
+fn test_token_bucket() {
+  let limiter = RateLimiter::new(2, Duration::from_millis(100));
+  let start = Instant::now();
+
+  assert_eq!(limiter.acquire(), Duration::from_secs(0));
+  assert_eq!(limiter.acquire(), Duration::from_secs(0));
+  assert!(!limiter.try_acquire());
+  assert!(limiter.acquire() > Duration::from_secs(0));
+  assert!(start.elapsed() >= Duration::from_millis(40));
+}
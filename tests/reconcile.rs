@@ -0,0 +1,187 @@
+extern crate coinbase;
+extern crate rust_decimal;
+
+use coinbase::prelude::*;
+use coinbase::reconcile::Issue;
+use coinbase::reconcile::Reconciler;
+use coinbase::reconcile::Report;
+use coinbase::server::MockServer;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+fn money(amount: f64, currency: &str) -> Money {
+  Money {
+    amount,
+    currency: currency.into(),
+    ..Default::default()
+  }
+}
+
+fn reference(id: &str) -> ResourceRef {
+  ResourceRef {
+    id: id.into(),
+    resource: ResourceType::Transaction,
+    ..Default::default()
+  }
+}
+
+fn buy(id: &str, status: TransferStatus, transaction: &str, amount: f64) -> Buy {
+  Buy {
+    meta: ResourceMeta {
+      id: id.into(),
+      ..Default::default()
+    },
+    status,
+    transaction: reference(transaction),
+    amount: money(amount, "BTC"),
+    ..Default::default()
+  }
+}
+
+fn transaction(id: &str, kind: TransactionType, status: TransactionStatus, amount: f64) -> Transaction {
+  Transaction {
+    meta: ResourceMeta {
+      id: id.into(),
+      ..Default::default()
+    },
+    kind,
+    status,
+    amount: money(amount, "BTC"),
+    ..Default::default()
+  }
+}
+
+#[test]
+fn test_reconciler() {
+  let buys: Vec<Buy> = vec![
+    buy("matched", TransferStatus::Completed, "t1", 1.0),
+    buy("amount", TransferStatus::Completed, "t2", 1.0),
+    buy("status", TransferStatus::Completed, "t3", 0.5),
+    buy("missing", TransferStatus::Completed, "t9", 0.5),
+    buy("unlinked", TransferStatus::Completed, "", 0.5),
+    buy("uncommitted", TransferStatus::Created, "", 0.5),
+    buy("duplicate", TransferStatus::Completed, "t1", 1.0),
+  ];
+
+  let sells: Vec<Sell> = vec![Sell {
+    meta: ResourceMeta {
+      id: "sell".into(),
+      ..Default::default()
+    },
+    status: TransferStatus::Completed,
+    transaction: reference("t4"),
+    amount: money(0.25, "BTC"),
+    ..Default::default()
+  }];
+
+  let transactions: Vec<Transaction> = vec![
+    transaction("t1", TransactionType::Buy, TransactionStatus::Completed, 1.0),
+    transaction("t2", TransactionType::Buy, TransactionStatus::Completed, 0.9),
+    transaction("t3", TransactionType::Buy, TransactionStatus::Pending, 0.5),
+    transaction("t4", TransactionType::Sell, TransactionStatus::Completed, -0.25),
+    transaction("t5", TransactionType::Sell, TransactionStatus::Completed, -0.1),
+    transaction("t6", TransactionType::Send, TransactionStatus::Completed, -0.1),
+  ];
+
+  let report: Report = Reconciler::new()
+    .buys("account", &buys)
+    .sells("account", &sells)
+    .transactions("account", &transactions)
+    .report();
+
+  let issues: HashMap<&str, &[Issue]> = report
+    .mismatches
+    .iter()
+    .map(|mismatch| (mismatch.id.as_str(), mismatch.issues.as_slice()))
+    .collect();
+
+  assert!(!report.is_clean());
+  assert_eq!(report.matched, 2);
+  assert_eq!(report.unlinked, 1);
+  assert_eq!(report.mismatches.len(), 5);
+  assert_eq!(
+    issues["amount"],
+    [Issue::Amount {
+      expected: Decimal::ONE,
+      actual: Decimal::new(9, 1),
+    }]
+  );
+  assert_eq!(
+    issues["status"],
+    [Issue::Status {
+      expected: "completed".into(),
+      actual: "pending".into(),
+    }]
+  );
+  assert_eq!(issues["missing"], [Issue::MissingTransaction]);
+  assert_eq!(issues["unlinked"], [Issue::Unlinked]);
+  assert_eq!(
+    issues["duplicate"],
+    [Issue::Duplicate {
+      other: "matched".into()
+    }]
+  );
+  assert_eq!(report.orphans.len(), 1);
+  assert_eq!(report.orphans[0].transaction, "t5");
+  assert_eq!(report.orphans[0].kind, "sell");
+}
+
+#[test]
+fn test_reconcile() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let usd: String = server.state().accounts[0].meta.id.clone();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+
+  let buy: HashMap<&str, &str> = vec![("amount", "0.5"), ("currency", "BTC")].into_iter().collect();
+  let sell: HashMap<&str, &str> = vec![("amount", "0.2"), ("currency", "BTC")].into_iter().collect();
+
+  client.create_buy(&btc, &buy).unwrap();
+  client.create_sell(&btc, &sell).unwrap();
+
+  let report: Report = client.reconcile(&btc).unwrap();
+
+  assert!(report.is_clean());
+  assert_eq!(report.matched, 2);
+
+  let deposit: Transaction = server
+    .state()
+    .add_transaction(&usd, TransactionType::FiatDeposit, TransactionStatus::Completed, 100.0)
+    .unwrap();
+
+  server
+    .state()
+    .add_transaction(
+      &usd,
+      TransactionType::FiatWithdrawal,
+      TransactionStatus::Completed,
+      -50.0,
+    )
+    .unwrap();
+
+  server.state().deposits.entry(usd.clone()).or_default().push(Deposit {
+    meta: ResourceMeta {
+      id: "deposit".into(),
+      ..Default::default()
+    },
+    status: TransferStatus::Completed,
+    transaction: reference(&deposit.meta.id),
+    amount: money(100.0, "EUR"),
+    ..Default::default()
+  });
+
+  let report: Report = client.reconcile_all().unwrap();
+
+  assert_eq!(report.matched, 2);
+  assert_eq!(report.mismatches.len(), 1);
+  assert_eq!(report.mismatches[0].resource, ResourceType::Deposit);
+  assert_eq!(
+    report.mismatches[0].issues,
+    vec![Issue::Currency {
+      expected: "EUR".into(),
+      actual: "USD".into(),
+    }]
+  );
+  assert_eq!(report.orphans.len(), 1);
+  assert_eq!(report.orphans[0].kind, "fiat_withdrawal");
+}
@@ -0,0 +1,166 @@
+extern crate coinbase;
+
+use coinbase::prelude::*;
+use coinbase::transport::*;
+use std::collections::HashMap;
+use std::io::Error as IO;
+use std::io::ErrorKind;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+
+const SPOT: &str = r#"{"data":{"base":"BTC","currency":"USD","amount":"7225.51"}}"#;
+const UNAVAILABLE: &str = r#"{"errors":[{"id":"internal_server_error","message":"Unavailable"}]}"#;
+const SEND: &str = r#"{"data":{"id":"tx","resource":"transaction","resource_path":"/v2/accounts/a/transactions/tx","type":"send","status":"pending","amount":{"amount":"-1","currency":"BTC"},"native_amount":{"amount":"-1","currency":"USD"},"details":{},"instant_exchange":false}}"#;
+
+#[derive(Debug)]
+struct Flaky {
+  failures: AtomicUsize,
+  inner: MockTransport,
+}
+
+impl Transport for Flaky {
+  fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+    if self.failures.load(Ordering::SeqCst) > 0 {
+      self.failures.fetch_sub(1, Ordering::SeqCst);
+      return Err(Error::IO(IO::new(ErrorKind::ConnectionReset, "connection reset")));
+    }
+
+    self.inner.send(request)
+  }
+}
+
+fn policy() -> RetryPolicy {
+  RetryPolicy {
+    base_delay: Duration::from_millis(0),
+    ..Default::default()
+  }
+}
+
+fn client(mock: &Arc<MockTransport>) -> Client {
+  Client::builder()
+    .transport(mock.clone())
+    .retry_policy(policy())
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn test_retry_get() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(503, UNAVAILABLE))
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  assert_eq!(client(&mock).spot_price("BTC", "USD").unwrap().data.amount, 7225.51);
+  assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn test_no_retry_by_default() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(503, UNAVAILABLE))
+    .mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  let client: Client = Client::builder().transport(mock.clone()).build().unwrap();
+
+  assert_eq!(
+    client.spot_price("BTC", "USD").unwrap().errors[0].id,
+    "internal_server_error"
+  );
+  assert_eq!(mock.requests().len(), 1);
+}
+
+#[test]
+fn test_retry_gives_up() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(503, UNAVAILABLE));
+
+  let response = client(&mock).spot_price("BTC", "USD").unwrap();
+
+  assert_eq!(response.errors[0].id, "internal_server_error");
+  assert_eq!(mock.requests().len(), 3);
+}
+
+#[test]
+fn test_no_retry_without_idem() {
+  let mock = Arc::new(MockTransport::new());
+  let data: HashMap<&str, &str> = vec![("type", "send"), ("to", "a@b.c")].into_iter().collect();
+
+  mock
+    .mock(
+      Method::POST,
+      "accounts/a/transactions",
+      HttpResponse::json(503, UNAVAILABLE),
+    )
+    .mock(Method::POST, "accounts/a/transactions", HttpResponse::json(201, SEND));
+
+  let response = client(&mock).create_transaction("a", &data).unwrap();
+
+  assert_eq!(response.errors[0].id, "internal_server_error");
+  assert_eq!(mock.requests().len(), 1);
+}
+
+#[test]
+fn test_retry_with_idem() {
+  let mock = Arc::new(MockTransport::new());
+  let data: HashMap<&str, &str> = vec![("type", "send"), ("to", "a@b.c"), ("idem", "1")]
+    .into_iter()
+    .collect();
+
+  mock
+    .mock(
+      Method::POST,
+      "accounts/a/transactions",
+      HttpResponse::json(503, UNAVAILABLE),
+    )
+    .mock(Method::POST, "accounts/a/transactions", HttpResponse::json(201, SEND));
+
+  let response = client(&mock).create_transaction("a", &data).unwrap();
+
+  assert_eq!(response.data.meta.id, "tx");
+  assert_eq!(mock.requests().len(), 2);
+}
+
+#[test]
+fn test_retry_transport_errors() {
+  let inner = MockTransport::new();
+
+  inner.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  let flaky = Flaky {
+    failures: AtomicUsize::new(2),
+    inner,
+  };
+
+  let client = Client::builder()
+    .transport(flaky)
+    .retry_policy(policy())
+    .build()
+    .unwrap();
+
+  assert_eq!(client.spot_price("BTC", "USD").unwrap().data.amount, 7225.51);
+}
+
+#[test]
+fn test_retry_delay() {
+  let policy = RetryPolicy {
+    base_delay: Duration::from_millis(100),
+    max_delay: Duration::from_millis(300),
+    jitter: false,
+    ..Default::default()
+  };
+
+  assert_eq!(policy.delay(1), Duration::from_millis(100));
+  assert_eq!(policy.delay(2), Duration::from_millis(200));
+  assert_eq!(policy.delay(3), Duration::from_millis(300));
+
+  let policy = RetryPolicy { jitter: true, ..policy };
+
+  assert!(policy.delay(2) <= Duration::from_millis(200));
+}
@@ -0,0 +1,137 @@
+extern crate chrono;
+extern crate coinbase;
+
+use chrono::NaiveDate;
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+use coinbase::server::State;
+use std::collections::HashMap;
+
+#[test]
+fn test_public_endpoints() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+
+  server.state().set_historic_price("BTC", "USD", "2019-01-01", 3_800.0);
+
+  assert_eq!(client.spot_price("BTC", "USD").unwrap().data.amount, 10_000.0);
+  assert_eq!(client.buy_price("BTC", "USD").unwrap().data.amount, 10_100.0);
+  assert_eq!(client.sell_price("BTC", "USD").unwrap().data.amount, 9_900.0);
+  assert_eq!(
+    client
+      .historic_spot_price("BTC", "USD", NaiveDate::from_ymd_opt(2019, 1, 1).unwrap())
+      .unwrap()
+      .data
+      .amount,
+    3_800.0
+  );
+  assert_eq!(client.rates(Some("BTC")).unwrap().data.rates["USD"], "10000");
+  assert_eq!(client.currencies().unwrap().data.len(), 3);
+}
+
+#[test]
+fn test_signature_verification() {
+  let server = MockServer::start_with(State::default(), Some(("key", "secret"))).unwrap();
+
+  let response = server.client().list_accounts().unwrap();
+
+  assert_eq!(response.data.len(), 3);
+  assert!(response.errors.is_empty());
+
+  let response = Client::builder()
+    .base_url(server.base_url())
+    .credentials("key", "wrong")
+    .build()
+    .unwrap()
+    .list_accounts()
+    .unwrap();
+
+  assert!(response.data.is_empty());
+  assert_eq!(response.errors[0].id, "authentication_error");
+}
+
+#[test]
+fn test_pagination() {
+  let server = MockServer::start().unwrap();
+
+  for index in 0..30 {
+    server.state().add_account(&format!("Vault {}", index), "BTC", 0.0);
+  }
+
+  let response = server.client().list_accounts().unwrap();
+  let pagination = response.pagination.unwrap();
+
+  assert_eq!(response.data.len(), 25);
+  assert_eq!(response.data[0].name, "Vault 29");
+  assert_eq!(pagination.limit, 25);
+  assert!(pagination.next_uri.unwrap().contains("starting_after="));
+  assert!(pagination.previous_uri.is_none());
+}
+
+#[test]
+fn test_transactions() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let account = client.list_accounts().unwrap().data[1].clone();
+
+  let send: HashMap<&str, &str> = vec![
+    ("type", "send"),
+    ("to", "satoshi@example.com"),
+    ("amount", "0.25"),
+    ("currency", "BTC"),
+    ("idem", "abc"),
+  ]
+  .into_iter()
+  .collect();
+  let created = client.create_transaction(&account.meta.id, &send).unwrap().data;
+
+  assert_eq!(created.amount.amount, -0.25);
+  assert_eq!(created.native_amount.amount, -2_500.0);
+  assert!(matches!(created.status, TransactionStatus::Pending));
+
+  let replayed = client.create_transaction(&account.meta.id, &send).unwrap().data;
+
+  assert_eq!(replayed.meta.id, created.meta.id);
+
+  server
+    .state()
+    .set_transaction_status(&account.meta.id, &created.meta.id, TransactionStatus::Completed);
+
+  let fetched = client.get_transaction(&account.meta.id, &created.meta.id).unwrap().data;
+
+  assert!(matches!(fetched.status, TransactionStatus::Completed));
+  assert_eq!(client.get_account(&account.meta.id).unwrap().data.balance.amount, 0.75);
+}
+
+#[test]
+fn test_buys_and_errors() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let account = client.list_accounts().unwrap().data[1].clone();
+
+  let order: HashMap<&str, &str> = vec![("amount", "0.5"), ("currency", "BTC")].into_iter().collect();
+  let buy = client.create_buy(&account.meta.id, &order).unwrap().data;
+
+  assert!(matches!(buy.status, TransferStatus::Completed));
+  assert_eq!(buy.subtotal.amount, 5_050.0);
+  assert_eq!(client.list_transactions(&account.meta.id).unwrap().data.len(), 1);
+  assert_eq!(client.get_account(&account.meta.id).unwrap().data.balance.amount, 1.5);
+
+  let order: HashMap<&str, &str> = vec![("amount", "0.25"), ("currency", "BTC")].into_iter().collect();
+  let sell = client.create_sell(&account.meta.id, &order).unwrap().data;
+  let transactions = client.list_transactions(&account.meta.id).unwrap().data;
+  let native = |id: &str| -> f64 {
+    transactions
+      .iter()
+      .find(|item| item.meta.id == id)
+      .map(|item| item.native_amount.amount)
+      .unwrap()
+  };
+
+  assert!(native(&buy.transaction.id) > 0.0);
+  assert!(native(&sell.transaction.id) < 0.0);
+
+  let response = client.get_account("missing").unwrap();
+
+  assert_eq!(response.errors[0].id, "not_found");
+}
@@ -0,0 +1,179 @@
+extern crate coinbase;
+
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+use coinbase::sync::AccountSnapshot;
+use coinbase::sync::JsonStore;
+use coinbase::sync::MemoryStore;
+use coinbase::sync::Snapshot;
+use coinbase::sync::Store;
+use coinbase::sync::SyncReport;
+use coinbase::transport::HttpRequest;
+use coinbase::transport::HttpResponse;
+use coinbase::transport::HttpTransport;
+use coinbase::transport::Transport;
+use std::collections::HashMap;
+use std::env::temp_dir;
+use std::fs::remove_file;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+// Rejects every paginated request as unauthorized and counts full listings
+// of `account` transactions.
+#[derive(Debug, Default)]
+struct Unauthorized {
+  inner: HttpTransport,
+  account: String,
+  listings: AtomicUsize,
+}
+
+impl Transport for Unauthorized {
+  fn send(&self, request: &HttpRequest) -> Result<HttpResponse, Error> {
+    if request.url.query().unwrap_or_default().contains("starting_after") {
+      return Ok(HttpResponse::json(
+        401,
+        r#"{"errors":[{"id":"authentication_error","message":"Invalid token"}]}"#,
+      ));
+    }
+
+    if request.url.path().ends_with(&format!("{}/transactions", self.account)) {
+      self.listings.fetch_add(1, Ordering::SeqCst);
+    }
+
+    self.inner.send(request)
+  }
+}
+
+#[test]
+fn test_sync() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let accounts: usize = server.state().accounts.len();
+  let mut store: MemoryStore = MemoryStore::new();
+
+  let buy: HashMap<&str, &str> = vec![("amount", "0.5"), ("currency", "BTC")].into_iter().collect();
+
+  client.create_buy(&btc, &buy).unwrap();
+
+  let report: SyncReport = client.sync(&mut store).unwrap();
+
+  assert_eq!(report.accounts, accounts);
+  assert_eq!(report.added, 2);
+  assert_eq!(store.snapshot().accounts.len(), accounts);
+
+  let stored: &AccountSnapshot = &store.snapshot().accounts[&btc];
+
+  assert_eq!(stored.transactions.len(), 1);
+  assert_eq!(stored.buys.len(), 1);
+  assert_eq!(stored.cursors.buys.as_deref(), Some(stored.buys[0].meta.id.as_str()));
+  assert!(stored.synced_at.is_some());
+
+  let report: SyncReport = client.sync(&mut store).unwrap();
+
+  assert_eq!((report.added, report.updated, report.rechecked), (0, 0, 0));
+
+  let pending: Transaction = server
+    .state()
+    .add_transaction(&btc, TransactionType::Send, TransactionStatus::Pending, 0.25)
+    .unwrap();
+
+  assert_eq!(client.sync(&mut store).unwrap().added, 1);
+
+  server
+    .state()
+    .set_transaction_status(&btc, &pending.meta.id, TransactionStatus::Completed)
+    .unwrap();
+
+  let report: SyncReport = client.sync(&mut store).unwrap();
+  let stored: &AccountSnapshot = &store.snapshot().accounts[&btc];
+
+  assert_eq!((report.added, report.updated, report.rechecked), (0, 1, 1));
+  assert!(matches!(stored.transactions[1].status, TransactionStatus::Completed));
+  assert_eq!(stored.account.balance.amount, server.state().accounts[1].balance.amount);
+}
+
+#[test]
+fn test_sync_removed() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let mut store: MemoryStore = MemoryStore::new();
+
+  server
+    .state()
+    .add_transaction(&btc, TransactionType::Send, TransactionStatus::Completed, 1.0)
+    .unwrap();
+
+  server
+    .state()
+    .add_transaction(&btc, TransactionType::Send, TransactionStatus::Pending, 0.5)
+    .unwrap();
+
+  client.sync(&mut store).unwrap();
+
+  server.state().transactions.get_mut(&btc).unwrap().pop();
+
+  let report: SyncReport = client.sync(&mut store).unwrap();
+  let stored: &AccountSnapshot = &store.snapshot().accounts[&btc];
+
+  assert_eq!((report.removed, report.rechecked), (1, 1));
+  assert_eq!(stored.transactions.len(), 1);
+  assert_eq!(
+    stored.cursors.transactions.as_deref(),
+    Some(stored.transactions[0].meta.id.as_str())
+  );
+}
+
+#[test]
+fn test_json_store() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let path: PathBuf = temp_dir().join(format!("coinbase-{}-sync.json", std::process::id()));
+
+  let sell: HashMap<&str, &str> = vec![("amount", "0.1"), ("currency", "BTC")].into_iter().collect();
+
+  client.create_sell(&btc, &sell).unwrap();
+
+  let mut store: JsonStore = JsonStore::new(&path);
+
+  assert!(store.load().unwrap().accounts.is_empty());
+  assert_eq!(client.sync(&mut store).unwrap().added, 2);
+
+  let snapshot: Snapshot = JsonStore::new(&path).load().unwrap();
+
+  assert_eq!(snapshot.accounts[&btc].sells.len(), 1);
+  assert_eq!(snapshot.accounts[&btc].transactions.len(), 1);
+  assert_eq!(client.sync(JsonStore::new(&path)).unwrap().added, 0);
+
+  remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_sync_cursor_errors() {
+  let server = MockServer::start().unwrap();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let transport: Arc<Unauthorized> = Arc::new(Unauthorized {
+    account: btc.clone(),
+    ..Default::default()
+  });
+  let client: Client = server.builder().transport(transport.clone()).build().unwrap();
+  let mut store: MemoryStore = MemoryStore::new();
+
+  let buy: HashMap<&str, &str> = vec![("amount", "0.5"), ("currency", "BTC")].into_iter().collect();
+
+  client.create_buy(&btc, &buy).unwrap();
+  client.sync(&mut store).unwrap();
+
+  let listings: usize = transport.listings.load(Ordering::SeqCst);
+
+  match client.sync(&mut store) {
+    Err(Error::API(errors)) => assert_eq!(errors[0].id, "authentication_error"),
+    other => panic!("{:?}", other),
+  }
+
+  assert_eq!(transport.listings.load(Ordering::SeqCst), listings);
+}
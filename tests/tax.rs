@@ -0,0 +1,198 @@
+extern crate chrono;
+extern crate coinbase;
+extern crate rust_decimal;
+
+use chrono::TimeZone;
+use chrono::Utc;
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+use coinbase::tax::EventKind;
+use coinbase::tax::Ledger;
+use coinbase::tax::Method;
+use coinbase::tax::TaxEvent;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+fn dec(value: &str) -> Decimal {
+  Decimal::from_str(value).unwrap()
+}
+
+fn event(id: &str, day: u32, kind: EventKind, amount: &str, value: &str) -> TaxEvent {
+  TaxEvent {
+    id: id.to_owned(),
+    time: Utc.with_ymd_and_hms(2019, 1, day, 0, 0, 0).unwrap(),
+    kind,
+    currency: "BTC".to_owned(),
+    amount: dec(amount),
+    native_value: Some(dec(value)),
+    fee: Decimal::ZERO,
+  }
+}
+
+fn ledger(method: Method, events: &[TaxEvent]) -> Ledger {
+  let mut ledger: Ledger = Ledger::new(method);
+
+  for event in events {
+    ledger.apply(event);
+  }
+
+  ledger
+}
+
+fn history() -> Vec<TaxEvent> {
+  vec![
+    event("a", 1, EventKind::Acquire, "1", "100"),
+    event("b", 2, EventKind::Acquire, "1", "300"),
+    event("c", 3, EventKind::Acquire, "1", "200"),
+    event("d", 4, EventKind::Dispose, "1.5", "600"),
+  ]
+}
+
+#[test]
+fn test_fifo() {
+  let ledger = ledger(Method::FIFO, &history());
+
+  assert_eq!(ledger.realized("BTC"), dec("350"));
+  assert_eq!(ledger.gains().len(), 2);
+  assert_eq!(ledger.gains()[0].lot.as_deref(), Some("a"));
+  assert_eq!(ledger.gains()[1].cost, dec("150"));
+  assert_eq!(ledger.gains()[1].proceeds, dec("200"));
+  assert_eq!(ledger.holdings("BTC"), dec("1.5"));
+  assert_eq!(ledger.cost_basis("BTC"), dec("350"));
+  assert_eq!(ledger.unrealized("BTC", dec("400")), dec("250"));
+}
+
+#[test]
+fn test_lifo() {
+  let ledger = ledger(Method::LIFO, &history());
+
+  assert_eq!(ledger.realized("BTC"), dec("250"));
+  assert_eq!(ledger.gains()[0].lot.as_deref(), Some("c"));
+  assert_eq!(ledger.lots("BTC").len(), 2);
+  assert_eq!(ledger.lots("BTC")[1].amount, dec("0.5"));
+}
+
+#[test]
+fn test_hifo() {
+  let ledger = ledger(Method::HIFO, &history());
+
+  assert_eq!(ledger.realized("BTC"), dec("200"));
+  assert_eq!(ledger.gains()[0].lot.as_deref(), Some("b"));
+  assert_eq!(ledger.gains()[1].lot.as_deref(), Some("c"));
+}
+
+#[test]
+fn test_average() {
+  let ledger = ledger(Method::Average, &history());
+
+  assert_eq!(ledger.realized("BTC"), dec("300"));
+  assert_eq!(ledger.lots("BTC").len(), 1);
+  assert_eq!(ledger.lots("BTC")[0].unit_cost(), dec("200"));
+  assert_eq!(ledger.cost_basis("BTC"), dec("300"));
+}
+
+#[test]
+fn test_disposal_without_lots() {
+  let mut events: Vec<TaxEvent> = history();
+
+  events.push(event("e", 5, EventKind::Dispose, "2", "1000"));
+
+  let ledger = ledger(Method::FIFO, &events);
+  let unmatched = ledger.gains().last().unwrap();
+
+  assert_eq!(unmatched.lot, None);
+  assert_eq!(unmatched.amount, dec("0.5"));
+  assert_eq!(unmatched.cost, Decimal::ZERO);
+  assert_eq!(unmatched.gain, dec("250"));
+  assert_eq!(ledger.holdings("BTC"), Decimal::ZERO);
+  assert_eq!(ledger.currencies().count(), 0);
+}
+
+#[test]
+fn test_client_ledger() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+
+  let buy: HashMap<&str, &str> = vec![("amount", "0.5"), ("currency", "BTC")].into_iter().collect();
+  let sell: HashMap<&str, &str> = vec![("amount", "0.2"), ("currency", "BTC")].into_iter().collect();
+
+  client.create_buy(&btc, &buy).unwrap();
+  server.state().set_price("BTC", "USD", 12_000.0);
+  client.create_sell(&btc, &sell).unwrap();
+
+  let events: Vec<TaxEvent> = client.tax_events().unwrap();
+
+  assert_eq!(events.len(), 2);
+  assert_eq!(events[0].kind, EventKind::Acquire);
+  assert_eq!(events[0].amount, dec("0.5"));
+  assert_eq!(events[0].fee, dec("75.75"));
+  assert_eq!(events[0].native_value, Some(dec("5125.75")));
+  assert_eq!(events[1].kind, EventKind::Dispose);
+
+  let ledger: Ledger = client.tax_ledger(Method::FIFO).unwrap();
+
+  assert_eq!(ledger.holdings("BTC"), dec("0.3"));
+  assert_eq!(ledger.gains().len(), 1);
+  assert_eq!(ledger.gains()[0].cost, dec("2050.3"));
+}
+
+#[test]
+fn test_historic_value() {
+  let server = MockServer::start().unwrap();
+  let eth: String = server.state().accounts[2].meta.id.clone();
+  let today: String = Utc::now().format("%Y-%m-%d").to_string();
+
+  {
+    let mut state = server.state();
+    let transaction = state
+      .add_transaction(&eth, TransactionType::Send, TransactionStatus::Completed, 2.0)
+      .unwrap();
+
+    state.transactions.get_mut(&eth).unwrap()[0].native_amount.amount = 0.0;
+    state.set_historic_price("ETH", "USD", &today, 150.0);
+
+    assert_eq!(transaction.amount.amount, 2.0);
+  }
+
+  let events: Vec<TaxEvent> = server.client().tax_events().unwrap();
+
+  assert_eq!(events.len(), 1);
+  assert_eq!(events[0].currency, "ETH");
+  assert_eq!(events[0].kind, EventKind::Acquire);
+  assert_eq!(events[0].native_value, Some(dec("300")));
+}
+
+#[test]
+fn test_non_native_total() {
+  let server = MockServer::start().unwrap();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+  let today: String = Utc::now().format("%Y-%m-%d").to_string();
+  let buy: HashMap<&str, &str> = vec![("amount", "0.5"), ("currency", "BTC")].into_iter().collect();
+
+  let buy: Buy = server.client().create_buy(&btc, &buy).unwrap().data;
+
+  let mut paid: Buy = buy.clone();
+
+  paid.total.currency = "EUR".to_owned();
+  paid.fee.currency = "EUR".to_owned();
+
+  let event: TaxEvent = TaxEvent::from_buy(&paid, "USD").unwrap();
+
+  assert_eq!(event.native_value, None);
+  assert_eq!(event.fee, Decimal::ZERO);
+  assert!(TaxEvent::from_buy(&buy, "USD").unwrap().native_value.is_some());
+
+  {
+    let mut state = server.state();
+
+    state.buys.get_mut(&btc).unwrap()[0] = paid;
+    state.set_historic_price("BTC", "USD", &today, 11_000.0);
+  }
+
+  let events: Vec<TaxEvent> = server.client().tax_events().unwrap();
+
+  assert_eq!(events.len(), 1);
+  assert_eq!(events[0].native_value, Some(dec("5500")));
+}
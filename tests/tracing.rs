@@ -0,0 +1,136 @@
+extern crate coinbase;
+
+use coinbase::endpoint::template;
+use coinbase::prelude::*;
+use coinbase::transport::*;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tracing::field::Field;
+use tracing::field::Visit;
+use tracing::span::Attributes;
+use tracing::span::Id;
+use tracing::span::Record;
+use tracing::Event;
+use tracing::Metadata;
+use tracing::Subscriber;
+
+const ERROR: &str = r#"{"errors":[{"id":"not_found","message":"Not found"}]}"#;
+const TRANSACTIONS: &str = r#"{"data":[]}"#;
+
+type Fields = HashMap<String, String>;
+
+struct Collect<'a>(&'a mut Fields);
+
+impl Visit for Collect<'_> {
+  fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+    self.0.insert(field.name().to_owned(), format!("{:?}", value));
+  }
+
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.0.insert(field.name().to_owned(), value.to_owned());
+  }
+}
+
+#[derive(Clone, Default)]
+struct Recorder {
+  spans: Arc<Mutex<Vec<(String, Fields)>>>,
+  events: Arc<Mutex<Vec<Fields>>>,
+}
+
+impl Subscriber for Recorder {
+  fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+    true
+  }
+
+  fn new_span(&self, span: &Attributes<'_>) -> Id {
+    let mut fields: Fields = Fields::new();
+    span.record(&mut Collect(&mut fields));
+
+    let mut spans = self.spans.lock().unwrap();
+    spans.push((span.metadata().name().to_owned(), fields));
+
+    Id::from_u64(spans.len() as u64)
+  }
+
+  fn record(&self, span: &Id, values: &Record<'_>) {
+    let mut spans = self.spans.lock().unwrap();
+    values.record(&mut Collect(&mut spans[span.into_u64() as usize - 1].1));
+  }
+
+  fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+  fn event(&self, event: &Event<'_>) {
+    let mut fields: Fields = Fields::new();
+    event.record(&mut Collect(&mut fields));
+    self.events.lock().unwrap().push(fields);
+  }
+
+  fn enter(&self, _span: &Id) {}
+
+  fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_endpoint_template() {
+  assert_eq!(template("time"), "time");
+  assert_eq!(template("accounts"), "accounts");
+  assert_eq!(template("/v2/accounts/abc/"), "v2/accounts/{id}");
+  assert_eq!(
+    template("accounts/abc/transactions/def/complete"),
+    "accounts/{id}/transactions/{id}/complete"
+  );
+  assert_eq!(template("prices/BTC-USD/spot?date=2019-01-01"), "prices/{pair}/spot");
+  assert_eq!(template("payment-methods/xyz"), "payment-methods/{id}");
+}
+
+#[test]
+fn test_tracing_spans() {
+  let recorder = Recorder::default();
+  let mock = MockTransport::new();
+
+  mock.mock(
+    Method::GET,
+    "accounts/abc/transactions",
+    HttpResponse::json(200, TRANSACTIONS),
+  );
+  mock.mock(Method::GET, "accounts/missing", HttpResponse::json(404, ERROR));
+
+  let client = Client::builder()
+    .credentials("key", "secret")
+    .transport(mock)
+    .build()
+    .unwrap();
+
+  tracing::subscriber::with_default(recorder.clone(), || {
+    client.list_transactions("abc").unwrap();
+    assert_eq!(client.get_account("missing").unwrap().errors[0].id, "not_found");
+  });
+
+  let spans = recorder.spans.lock().unwrap();
+
+  assert_eq!(spans.len(), 2);
+  assert_eq!(spans[0].0, "coinbase.request");
+  assert_eq!(spans[0].1["endpoint"], "accounts/{id}/transactions");
+  assert_eq!(spans[0].1["method"], "GET");
+  assert_eq!(spans[0].1["status"], "200");
+  assert_eq!(spans[0].1["attempts"], "1");
+  assert!(spans[0].1.contains_key("latency_ms"));
+  assert!(!spans[0].1.contains_key("error_id"));
+
+  assert_eq!(spans[1].1["endpoint"], "accounts/{id}");
+  assert_eq!(spans[1].1["status"], "404");
+  assert_eq!(spans[1].1["error_id"], "not_found");
+
+  for fields in spans
+    .iter()
+    .map(|(_, fields)| fields)
+    .chain(recorder.events.lock().unwrap().iter())
+  {
+    for value in fields.values() {
+      assert!(!value.contains("secret"));
+      assert!(!value.contains("key"));
+    }
+  }
+}
@@ -0,0 +1,125 @@
+extern crate coinbase;
+
+use coinbase::prelude::*;
+use coinbase::transport::*;
+use std::sync::Arc;
+
+const SPOT: &str = r#"{"data":{"base":"BTC","currency":"USD","amount":"7225.51"}}"#;
+
+#[test]
+fn test_mock_response() {
+  let mock = MockTransport::new();
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  let client = Client::builder().transport(mock).build().unwrap();
+  let response = client.spot_price("BTC", "USD").unwrap();
+
+  assert_eq!(response.data.amount, 7225.51);
+  assert_eq!(response.data.base, Some(String::from("BTC")));
+}
+
+#[test]
+fn test_mock_records_signed_requests() {
+  let mock = Arc::new(MockTransport::new());
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  let client = Client::builder()
+    .credentials("key", "secret")
+    .version("2020-01-01")
+    .transport(mock.clone())
+    .build()
+    .unwrap();
+
+  client.spot_price("BTC", "USD").unwrap();
+
+  let requests = mock.requests();
+
+  assert_eq!(requests.len(), 1);
+  assert_eq!(requests[0].method, Method::GET);
+  assert_eq!(
+    requests[0].url.as_str(),
+    "https://api.coinbase.com/v2/prices/BTC-USD/spot"
+  );
+  assert_eq!(requests[0].headers["CB-VERSION"], "2020-01-01");
+  assert_eq!(requests[0].headers["CB-ACCESS-KEY"], "key");
+  assert!(requests[0].headers.contains_key("CB-ACCESS-SIGN"));
+}
+
+#[test]
+fn test_mock_query_and_sequence() {
+  let mock = MockTransport::new();
+
+  mock
+    .mock(
+      Method::GET,
+      "exchange-rates?currency=EUR",
+      HttpResponse::json(200, r#"{"data":{"currency":"EUR","rates":{"BTC":"0.0001"}}}"#),
+    )
+    .mock(
+      Method::GET,
+      "exchange-rates?currency=EUR",
+      HttpResponse::json(200, r#"{"data":{"currency":"EUR","rates":{"BTC":"0.0002"}}}"#),
+    );
+
+  let client = Client::builder().transport(mock).build().unwrap();
+
+  assert_eq!(client.rates(Some("EUR")).unwrap().data.rates["BTC"], "0.0001");
+  assert_eq!(client.rates(Some("EUR")).unwrap().data.rates["BTC"], "0.0002");
+  assert_eq!(client.rates(Some("EUR")).unwrap().data.rates["BTC"], "0.0002");
+  assert!(matches!(client.rates(Some("USD")), Err(Error::Unmatched { .. })));
+}
+
+#[test]
+fn test_mock_full_path() {
+  let mock = MockTransport::new();
+
+  mock.mock(Method::GET, "spot", HttpResponse::json(200, SPOT));
+
+  let client = Client::builder().transport(mock).build().unwrap();
+
+  assert!(matches!(client.spot_price("BTC", "USD"), Err(Error::Unmatched { .. })));
+
+  let mock = MockTransport::new().base_path("/api/v2/");
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  let client = Client::builder()
+    .base_url("http://localhost/api/v2/")
+    .transport(mock)
+    .build()
+    .unwrap();
+
+  assert_eq!(client.spot_price("BTC", "USD").unwrap().data.amount, 7225.51);
+}
+
+#[test]
+fn test_cassette_record_and_replay() {
+  let path = std::env::temp_dir().join(format!("coinbase-{}-cassette.json", std::process::id()));
+  let mock = MockTransport::new();
+
+  mock.mock(Method::GET, "prices/BTC-USD/spot", HttpResponse::json(200, SPOT));
+
+  let client = Client::builder()
+    .credentials("key", "secret")
+    .transport(Cassette::record(&path, mock))
+    .build()
+    .unwrap();
+
+  client.spot_price("BTC", "USD").unwrap();
+
+  let recorded = std::fs::read_to_string(&path).unwrap();
+
+  assert!(recorded.contains("/v2/prices/BTC-USD/spot"));
+  assert!(!recorded.to_lowercase().contains("cb-access"));
+
+  let cassette = Arc::new(Cassette::replay(&path).unwrap());
+  let client = Client::builder().transport(cassette.clone()).build().unwrap();
+
+  std::fs::remove_file(&path).unwrap();
+
+  assert_eq!(client.spot_price("BTC", "USD").unwrap().data.amount, 7225.51);
+  assert!(cassette.is_exhausted());
+  assert!(matches!(client.spot_price("BTC", "USD"), Err(Error::Unmatched { .. })));
+}
@@ -0,0 +1,129 @@
+extern crate coinbase;
+
+use coinbase::prelude::*;
+use coinbase::server::MockServer;
+use coinbase::watch::Watched;
+use coinbase::watch::Watcher;
+use serde_json::Value;
+use std::thread;
+use std::time::Duration;
+
+fn watcher() -> Watcher {
+  Watcher::new()
+    .interval(Duration::from_millis(5))
+    .max_interval(Duration::from_millis(20))
+    .timeout(Duration::from_secs(5))
+}
+
+fn network(server: &MockServer, account: &str, transaction: &str, confirmations: u64) {
+  let mut state = server.state();
+
+  let item: &mut Transaction = state
+    .transactions
+    .get_mut(account)
+    .and_then(|items| items.iter_mut().find(|item| item.meta.id == transaction))
+    .unwrap();
+
+  let mut network: ValueMap = ValueMap::new();
+
+  network.insert("status".into(), Value::String("confirmed".into()));
+  network.insert("confirmations".into(), Value::from(confirmations));
+  item.network = Some(network);
+}
+
+#[test]
+fn test_watch_transaction() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+
+  let transaction: Transaction = server
+    .state()
+    .add_transaction(&btc, TransactionType::Send, TransactionStatus::Pending, -0.1)
+    .unwrap();
+
+  let mut seen: Vec<TransactionStatus> = Vec::new();
+
+  let watched: Watched = thread::scope(|scope| {
+    scope.spawn(|| {
+      thread::sleep(Duration::from_millis(100));
+      server
+        .state()
+        .set_transaction_status(&btc, &transaction.meta.id, TransactionStatus::Completed)
+        .unwrap();
+    });
+
+    client
+      .watch_transaction(&btc, &transaction.meta.id, &watcher(), |transaction| {
+        seen.push(transaction.status.clone())
+      })
+      .unwrap()
+  });
+
+  assert!(!watched.timed_out);
+  assert!(watched.polls > 2);
+  assert_eq!(watched.transaction.status, TransactionStatus::Completed);
+  assert_eq!(seen, vec![TransactionStatus::Pending, TransactionStatus::Completed]);
+}
+
+#[test]
+fn test_watch_confirmations() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+
+  let transaction: Transaction = server
+    .state()
+    .add_transaction(&btc, TransactionType::Send, TransactionStatus::Completed, -0.1)
+    .unwrap();
+
+  network(&server, &btc, &transaction.meta.id, 1);
+
+  let mut seen: Vec<Option<u64>> = Vec::new();
+
+  let watched: Watched = thread::scope(|scope| {
+    scope.spawn(|| {
+      thread::sleep(Duration::from_millis(50));
+      network(&server, &btc, &transaction.meta.id, 2);
+      thread::sleep(Duration::from_millis(50));
+      network(&server, &btc, &transaction.meta.id, 6);
+    });
+
+    client
+      .watch_transaction(&btc, &transaction.meta.id, &watcher().confirmations(6), |transaction| {
+        seen.push(coinbase::watch::confirmations(transaction))
+      })
+      .unwrap()
+  });
+
+  assert!(!watched.timed_out);
+  assert_eq!(seen, vec![Some(1), Some(2), Some(6)]);
+}
+
+#[test]
+fn test_watch_timeout() {
+  let server = MockServer::start().unwrap();
+  let client = server.client();
+  let btc: String = server.state().accounts[1].meta.id.clone();
+
+  let transaction: Transaction = server
+    .state()
+    .add_transaction(&btc, TransactionType::Send, TransactionStatus::WaitingForClearing, -0.1)
+    .unwrap();
+
+  let mut changes: usize = 0;
+
+  let watched: Watched = client
+    .watch_transaction(
+      &btc,
+      &transaction.meta.id,
+      &watcher().timeout(Duration::from_millis(60)),
+      |_| changes += 1,
+    )
+    .unwrap();
+
+  assert!(watched.timed_out);
+  assert!(watched.elapsed >= Duration::from_millis(60));
+  assert_eq!(changes, 1);
+  assert_eq!(watched.transaction.status, TransactionStatus::WaitingForClearing);
+}